@@ -7,6 +7,17 @@ class SentenceTokenizer:
     def __init__(self, eos: str = None, patterns: List[str] = None):
         pass
 
+    @staticmethod
+    def from_config(path: str) -> "SentenceTokenizer":
+        """Loads a tokenizer from a TOML or JSON config file shared with the Rust crate."""
+        pass
+
+    @staticmethod
+    def from_preset(name: str) -> "SentenceTokenizer":
+        """One of "wikipedia", "fiction", "social-media", "legal", "academic",
+        "simplified-chinese" or "traditional-chinese", the names of the Rust `Preset`."""
+        pass
+
     def tokenize(self, document: str) -> List[str]:
         pass
 
@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use saku::{SentenceTokenizer, SentenceTokenizerBuilder};
+use saku::{Preset, SentenceTokenizer, SentenceTokenizerBuilder};
 
 #[pyclass(name = "SentenceTokenizer")]
 #[pyo3(text_signature = "(self, eos, patterns)")]
@@ -35,6 +36,25 @@ impl PySentenceTokenizer {
         Self { tokenizer }
     }
 
+    #[staticmethod]
+    #[pyo3(text_signature = "(path)")]
+    fn from_config(path: &str) -> PyResult<Self> {
+        let tokenizer = SentenceTokenizer::from_config_file(path)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { tokenizer })
+    }
+
+    #[staticmethod]
+    #[pyo3(text_signature = "(name)")]
+    fn from_preset(name: &str) -> PyResult<Self> {
+        let preset: Preset = name
+            .parse()
+            .map_err(|e: saku::UnknownPreset| PyValueError::new_err(e.to_string()))?;
+        Ok(Self {
+            tokenizer: SentenceTokenizer::preset(preset),
+        })
+    }
+
     #[inline(always)]
     pub fn tokenize<'a>(&self, document: &'a str) -> Vec<Cow<'a, str>> {
         self.tokenizer.tokenize(document)
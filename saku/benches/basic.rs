@@ -1,7 +1,7 @@
 extern crate criterion;
 use criterion::{criterion_group, criterion_main, Criterion};
 use pprof::criterion::{Output, PProfProfiler};
-use saku::SentenceTokenizer;
+use saku::{Preset, SentenceTokenizer};
 use std::{fs, time::Duration};
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -11,6 +11,13 @@ fn criterion_benchmark(c: &mut Criterion) {
         let text = fs::read_to_string(data_path).unwrap();
         b.iter(|| tokenizer.tokenize(&text))
     });
+    // the ASCII brackets of the preset take the scanner past three single-byte chars
+    c.bench_function("basic-tokenize-wikipedia", |b| {
+        let data_path = "../data/medium.txt";
+        let tokenizer = SentenceTokenizer::preset(Preset::Wikipedia);
+        let text = fs::read_to_string(data_path).unwrap();
+        b.iter(|| tokenizer.tokenize(&text))
+    });
 }
 
 criterion_group! {
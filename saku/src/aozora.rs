@@ -0,0 +1,105 @@
+use crate::markup::Stripped;
+use crate::soft_wrap::lines;
+use core::ops::Range;
+
+// The body of an Aozora Bunko text, without the title and the notes on notation that end with a
+// line of dashes, and without the bibliographic footer starting with `底本：`.
+fn body(document: &str) -> Range<usize> {
+    let lines = lines(document);
+    let is_separator = |&(start, end): &(usize, usize)| {
+        let line = document[start..end].trim();
+        line.len() >= 10 && line.chars().all(|ch| ch == '-')
+    };
+    let notes = lines.iter().position(is_separator).filter(|&k| {
+        lines.get(k + 1).is_some_and(|&(start, _)| {
+            document[start..].starts_with("【テキスト中に現れる記号について】")
+        })
+    });
+    let start = notes
+        .and_then(|k| {
+            lines[k + 1..]
+                .iter()
+                .position(is_separator)
+                .map(|n| k + 1 + n)
+        })
+        .and_then(|k| lines.get(k + 1))
+        .map_or(0, |&(start, _)| start);
+    let end = lines
+        .iter()
+        .find(|&&(line_start, _)| {
+            line_start >= start && document[line_start..].starts_with("底本：")
+        })
+        .map_or(document.len(), |&(line_start, _)| line_start);
+    start..end
+}
+
+// The end of the `［＃…］` annotation starting at `start`, after its `］`.
+fn annotation_end(document: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, ch) in document[start..].char_indices() {
+        match ch {
+            '［' => depth += 1,
+            '］' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i + ch.len_utf8());
+                }
+            }
+            '\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+// Removes the header, the footer and the annotations of an Aozora Bunko text, and its ruby unless
+// `keep_ruby`. The ruby of `｜漢字《かんじ》` is kept as `漢字《かんじ》`.
+pub(crate) fn strip(document: &str, keep_ruby: bool) -> Stripped {
+    let body = body(document);
+    let mut stripped = Stripped::new(body.len());
+    // the start of the run being copied
+    let mut start = body.start;
+    let mut skip_to = body.start;
+    for (i, ch) in document[body.clone()].char_indices() {
+        let i = i + body.start;
+        if i < skip_to {
+            continue;
+        }
+        let line_end = document[i..].find('\n').map_or(document.len(), |j| i + j);
+        let end = match ch {
+            // the start of the text under a ruby
+            '｜' if document[i..line_end].contains('《') => Some(i + ch.len_utf8()),
+            '《' if !keep_ruby => document[i..line_end]
+                .find('》')
+                .map(|j| i + j + '》'.len_utf8()),
+            '［' if document[i + ch.len_utf8()..].starts_with('＃') => {
+                annotation_end(document, i)
+            }
+            _ => None,
+        };
+        if let Some(end) = end {
+            stripped.push(document, start..i);
+            start = end.min(body.end);
+            skip_to = start;
+        }
+    }
+    stripped.push(document, start..body.end);
+    stripped
+}
+
+#[cfg(test)]
+mod test {
+    use super::strip;
+
+    #[test]
+    fn strips_markup() {
+        let document = "吾輩は猫である\n夏目漱石\n\n-------------------------------------------------------\n【テキスト中に現れる記号について】\n《》：ルビ\n-------------------------------------------------------\n\n　吾輩《わがはい》は猫である。｜名前《なまえ》はまだ無い［＃「無い」に傍点］。\n［＃改ページ］\n\n底本：「夏目漱石全集1」ちくま文庫\n";
+        let stripped = strip(document, false);
+        assert_eq!("\n　吾輩は猫である。名前はまだ無い。\n\n\n", stripped.text);
+        let stripped = strip(document, true);
+        assert_eq!(
+            "\n　吾輩《わがはい》は猫である。名前《なまえ》はまだ無い。\n\n\n",
+            stripped.text
+        );
+    }
+}
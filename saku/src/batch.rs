@@ -0,0 +1,35 @@
+use crate::{Sentence, SentenceTokenizer};
+use alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Tokenizes many documents at once.
+// With the `parallel` feature, the documents are spread over the rayon thread pool; the results
+// are always in the order of `documents`.
+impl SentenceTokenizer {
+    pub fn tokenize_batch<'a, D>(&self, documents: &'a [D]) -> Vec<Vec<Cow<'a, str>>>
+    where
+        D: AsRef<str> + Sync,
+    {
+        #[cfg(feature = "parallel")]
+        let documents = documents.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let documents = documents.iter();
+        documents
+            .map(|document| self.tokenize(document.as_ref()))
+            .collect()
+    }
+
+    pub fn tokenize_batch_with_spans<'a, D>(&self, documents: &'a [D]) -> Vec<Vec<Sentence<'a>>>
+    where
+        D: AsRef<str> + Sync,
+    {
+        #[cfg(feature = "parallel")]
+        let documents = documents.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let documents = documents.iter();
+        documents
+            .map(|document| self.tokenize_with_spans(document.as_ref()))
+            .collect()
+    }
+}
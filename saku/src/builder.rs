@@ -1,70 +1,330 @@
-use crate::{CharTable, ControlFlow, SentenceTokenizer};
+use crate::{
+    CharTable, LazyScanner, Markup, Normalization, Preset, SentenceTokenizer, WhitespacePolicy,
+};
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
 
-const DEFAULT_EOS: char = '。';
-const DEFAULT_LEFT_PATTERNS: [char; 3] = ['（', '「', '『'];
-const DEFAULT_RIGHT_PATTERNS: [char; 3] = ['）', '」', '』'];
+pub(crate) const DEFAULT_EOS: char = '。';
+pub(crate) const DEFAULT_PATTERNS: [[char; 2]; 3] = [['（', '）'], ['「', '」'], ['『', '』']];
+
+// How `SentenceTokenizer::tokenize` treats line breaks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum LineBreaks {
+    // Line breaks are removed and the lines are joined into sentences.
+    #[default]
+    Join,
+    // Line breaks end sentences, as in `SentenceTokenizer::tokenize_raw`.
+    Split,
+    // Each line break is either removed as a soft wrap or ends a sentence, judging from the lengths
+    // of the lines, indentation, blank lines and terminators at line ends.
+    // Suited to text extracted from PDFs and other fixed-width sources.
+    Auto,
+}
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct SentenceTokenizerBuilder {
-    pub(crate) eos: char,
-    pub(crate) left_patterns: Vec<char>,
-    pub(crate) right_patterns: Vec<char>,
+    pub(crate) eos: Vec<char>,
+    pub(crate) patterns: Vec<[char; 2]>,
+    pub(crate) line_breaks: LineBreaks,
+    pub(crate) glue_eos: bool,
+    pub(crate) normalization: Normalization,
+    pub(crate) whitespace: WhitespacePolicy,
+    pub(crate) headings: bool,
+    pub(crate) list_items: bool,
+    pub(crate) legal: bool,
+    pub(crate) markup: Markup,
+    pub(crate) keep_ruby: bool,
+    pub(crate) speech_endings: Vec<String>,
+    pub(crate) social_media: bool,
+    pub(crate) quote_eos: bool,
+    pub(crate) latin: bool,
+}
+
+impl Default for SentenceTokenizerBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SentenceTokenizerBuilder {
     #[inline(always)]
     pub fn new() -> SentenceTokenizerBuilder {
         SentenceTokenizerBuilder {
-            eos: DEFAULT_EOS,
-            left_patterns: DEFAULT_LEFT_PATTERNS.to_vec(),
-            right_patterns: DEFAULT_RIGHT_PATTERNS.to_vec(),
+            eos: vec![DEFAULT_EOS],
+            patterns: DEFAULT_PATTERNS.to_vec(),
+            line_breaks: LineBreaks::Join,
+            glue_eos: false,
+            normalization: Normalization::None,
+            whitespace: WhitespacePolicy::new(),
+            headings: false,
+            list_items: false,
+            legal: false,
+            markup: Markup::None,
+            keep_ruby: false,
+            speech_endings: Vec::new(),
+            social_media: false,
+            quote_eos: false,
+            latin: false,
         }
     }
+
+    #[inline(always)]
+    pub fn preset(preset: Preset) -> SentenceTokenizerBuilder {
+        preset.builder()
+    }
+
+    #[inline(always)]
+    pub fn get_eos(&self) -> &[char] {
+        &self.eos
+    }
+
+    #[inline(always)]
+    pub fn get_patterns(&self) -> &[[char; 2]] {
+        &self.patterns
+    }
+
+    #[inline(always)]
+    pub fn get_line_breaks(&self) -> LineBreaks {
+        self.line_breaks
+    }
+
+    #[inline(always)]
+    pub fn get_glue_eos(&self) -> bool {
+        self.glue_eos
+    }
+
+    #[inline(always)]
+    pub fn get_normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    #[inline(always)]
+    pub fn get_whitespace(&self) -> WhitespacePolicy {
+        self.whitespace
+    }
+
+    #[inline(always)]
+    pub fn get_headings(&self) -> bool {
+        self.headings
+    }
+
+    #[inline(always)]
+    pub fn get_list_items(&self) -> bool {
+        self.list_items
+    }
+
+    #[inline(always)]
+    pub fn get_legal(&self) -> bool {
+        self.legal
+    }
+
+    #[inline(always)]
+    pub fn get_markup(&self) -> Markup {
+        self.markup
+    }
+
+    #[inline(always)]
+    pub fn get_keep_ruby(&self) -> bool {
+        self.keep_ruby
+    }
+
+    #[inline(always)]
+    pub fn get_speech_endings(&self) -> &[String] {
+        &self.speech_endings
+    }
+
+    #[inline(always)]
+    pub fn get_social_media(&self) -> bool {
+        self.social_media
+    }
+
+    #[inline(always)]
+    pub fn get_quote_eos(&self) -> bool {
+        self.quote_eos
+    }
+
+    #[inline(always)]
+    pub fn get_latin(&self) -> bool {
+        self.latin
+    }
 }
 
 impl SentenceTokenizerBuilder {
     #[inline(always)]
     pub fn eos(self, eos: char) -> SentenceTokenizerBuilder {
         SentenceTokenizerBuilder {
-            eos,
-            left_patterns: self.left_patterns,
-            right_patterns: self.right_patterns,
+            eos: vec![eos],
+            ..self
+        }
+    }
+
+    // Registers several terminators at once, e.g. `&['。', '！', '？']`.
+    // The first one is exposed as `SentenceTokenizer::eos`.
+    #[inline(always)]
+    pub fn eos_chars(self, eos: &[char]) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            eos: eos.to_vec(),
+            ..self
         }
     }
 
     #[inline(always)]
     pub fn patterns(self, patterns: &[[char; 2]]) -> SentenceTokenizerBuilder {
-        let left_patterns: Vec<char> = patterns.iter().map(|p| p[0]).collect();
-        let right_patterns: Vec<char> = patterns.iter().map(|p| p[1]).collect();
         SentenceTokenizerBuilder {
-            eos: self.eos,
-            left_patterns,
-            right_patterns,
+            patterns: patterns.to_vec(),
+            ..self
         }
     }
 
     #[inline(always)]
-    pub fn build(&self) -> SentenceTokenizer {
-        let eos = self.eos;
-        let num_parens: u8 = self.left_patterns.len() as u8;
+    pub fn line_breaks(self, line_breaks: LineBreaks) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            line_breaks,
+            ..self
+        }
+    }
+
+    // Keeps consecutive terminators such as `！？` in the sentence they end.
+    #[inline(always)]
+    pub fn glue_eos(self, glue_eos: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { glue_eos, ..self }
+    }
+
+    // Also recognizes the width and compatibility variants of the terminators and brackets.
+    #[inline(always)]
+    pub fn normalization(self, normalization: Normalization) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            normalization,
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    pub fn whitespace(self, whitespace: WhitespacePolicy) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { whitespace, ..self }
+    }
+
+    // Ends sentences around heading lines such as `第1章　はじめに`, which have no terminator,
+    // and marks them with `SentenceKind::Heading`.
+    #[inline(always)]
+    pub fn headings(self, headings: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { headings, ..self }
+    }
+
+    // Starts a sentence at each list marker that begins a line, such as `・`, `①`, `(1)` or `1.`,
+    // and marks it with `SentenceKind::ListItem`. Brackets and terminators in markers are ignored.
+    #[inline(always)]
+    pub fn list_items(self, list_items: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { list_items, ..self }
+    }
+
+    // Splits statutes and contracts at the lines starting with an article, paragraph or item
+    // number, such as `第一条`, `２` or `一`, keeping captions such as `（目的）` with their article,
+    // and sets `Sentence::provision`.
+    #[inline(always)]
+    pub fn legal(self, legal: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { legal, ..self }
+    }
 
-        let mut char_table: CharTable = CharTable::default();
-        char_table.insert(eos, ControlFlow::Eos);
-        for (flag_id, &l) in self.left_patterns.iter().enumerate() {
-            char_table.insert(l, ControlFlow::LeftParens(flag_id as u8));
+    #[inline(always)]
+    pub fn markup(self, markup: Markup) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { markup, ..self }
+    }
+
+    // Keeps the ruby of `Markup::Aozora` texts, as in `吾輩《わがはい》`, instead of removing it.
+    #[inline(always)]
+    pub fn keep_ruby(self, keep_ruby: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { keep_ruby, ..self }
+    }
+
+    // Also ends sentences of unpunctuated speech, such as ASR output, after these sentence-final
+    // forms, e.g. `SPEECH_ENDINGS`, when they are followed by whitespace or by a word that starts
+    // a sentence such as `それで`. Particles such as `ね` or `よ` after them stay in the sentence.
+    // Terminators still end sentences. An empty list disables it.
+    #[inline(always)]
+    pub fn speech_endings(self, endings: &[&str]) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            speech_endings: endings.iter().map(|&ending| String::from(ending)).collect(),
+            ..self
         }
-        for (flag_id, &r) in self.right_patterns.iter().enumerate() {
-            char_table.insert(r, ControlFlow::RightParens(flag_id as u8));
+    }
+
+    // Ends the sentences of posts and chats at emoji, symbols such as `♪`, kaomoji such as
+    // `(´・ω・｀)`, laughter such as `（笑）` or `ｗｗｗ`, and ideographic spaces after text.
+    // They are kept at the end of the sentence they close, with any terminators around them, and
+    // the brackets of kaomoji are not matched.
+    #[inline(always)]
+    pub fn social_media(self, social_media: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            social_media,
+            ..self
+        }
+    }
+
+    // Ends the sentence after a closing bracket that follows a terminator, as in Chinese
+    // `他说：“今天很好。”然后走了。`, instead of ignoring the terminator inside the brackets.
+    #[inline(always)]
+    pub fn quote_eos(self, quote_eos: bool) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder { quote_eos, ..self }
+    }
+
+    // Splits the English and other Latin-script text of bilingual documents with its own rules:
+    // `.`, `?`, `!` and `…` after a Latin word end a sentence only when followed by whitespace and
+    // an uppercase letter or a quote, or by Japanese text, so that abbreviations such as `Dr.`,
+    // initials, decimals and ellipses inside sentences are kept. Other text keeps the terminators
+    // of the tokenizer. Turning it on also sets `WhitespacePolicy::trim_sentences`, as Latin
+    // sentences are separated by spaces; a later `whitespace` can turn it off again.
+    #[inline(always)]
+    pub fn latin(self, latin: bool) -> SentenceTokenizerBuilder {
+        let whitespace = match latin {
+            true => self.whitespace.trim_sentences(true),
+            false => self.whitespace,
+        };
+        SentenceTokenizerBuilder {
+            latin,
+            whitespace,
+            ..self
         }
-        char_table.insert('\n', ControlFlow::LineBreaks);
-        char_table.insert('\r', ControlFlow::LineBreaks);
-        let eos_size = eos.len_utf8();
+    }
+
+    #[inline(always)]
+    pub fn build(&self) -> SentenceTokenizer {
+        let eos = self.eos.first().copied().unwrap_or('\0');
+        let num_parens: u8 = self.patterns.len() as u8;
+        let aliases = self.normalization.aliases(&self.eos, &self.patterns);
+        let char_table = CharTable::new(&self.eos, &self.patterns, &aliases);
+        let scanner = LazyScanner::build(&char_table);
 
         SentenceTokenizer {
             eos,
-            eos_size,
+            eos_chars: Cow::Owned(self.eos.clone()),
+            patterns: Cow::Owned(self.patterns.clone()),
+            line_breaks: self.line_breaks,
+            glue_eos: self.glue_eos,
+            normalization: self.normalization,
+            whitespace: self.whitespace,
+            headings: self.headings,
+            list_items: self.list_items,
+            legal: self.legal,
+            markup: self.markup,
+            keep_ruby: self.keep_ruby,
+            speech_endings: self.speech_endings.clone(),
+            social_media: self.social_media,
+            quote_eos: self.quote_eos,
+            latin: self.latin,
             num_parens,
             char_table,
+            scanner,
         }
     }
 }
@@ -80,4 +340,17 @@ mod test {
             .build();
         assert_eq!('。', tokenizer.eos);
     }
+
+    #[test]
+    fn const_tokenizer_matches_builder() {
+        static TOKENIZER: SentenceTokenizer = SentenceTokenizer::new();
+        let document = "「どーも。」で囲んで（います。）\nはい。いいえ";
+
+        let built = SentenceTokenizerBuilder::new().build();
+        assert_eq!(built.tokenize(document), TOKENIZER.tokenize(document));
+        assert_eq!(
+            built.tokenize_raw(document),
+            TOKENIZER.tokenize_raw(document)
+        );
+    }
 }
@@ -1,38 +1,212 @@
 use crate::ControlFlow;
+use alloc::{borrow::Cow, vec, vec::Vec};
 
+pub(crate) type Slot = Option<(char, ControlFlow)>;
+
+// A perfect hash table from the registered control chars to their flows.
+// Each char is hashed by a multiplicative hash, and `seed`/`bits` are searched so that no two
+// registered chars share a slot. Lookups are a multiply, a shift and a compare, and the table
+// stays a few dozen slots whichever codepoints are registered.
+// The layout is computed by `const fn`s, so tables can also be built at compile time.
 #[derive(Clone, Debug)]
 pub struct CharTable {
-    max_idx: usize,
-    table: Vec<Option<ControlFlow>>,
+    seed: u32,
+    bits: u32,
+    slots: Cow<'static, [Slot]>,
 }
 
-impl Default for CharTable {
-    #[inline]
-    fn default() -> Self {
-        Self {
-            max_idx: 0,
-            table: vec![None],
+const SEEDS_PER_SIZE: u32 = 64;
+
+// The registered chars in insertion order: aliases (variants of the other chars recognized by
+// normalization), terminators, left parens, right parens and line breaks.
+// A later entry overrides an earlier one for the same char.
+#[inline(always)]
+const fn num_entries(
+    eos: &[char],
+    patterns: &[[char; 2]],
+    aliases: &[(char, ControlFlow)],
+) -> usize {
+    aliases.len() + eos.len() + 2 * patterns.len() + 2
+}
+
+#[inline(always)]
+const fn entry(
+    eos: &[char],
+    patterns: &[[char; 2]],
+    aliases: &[(char, ControlFlow)],
+    k: usize,
+) -> (char, ControlFlow) {
+    if k < aliases.len() {
+        return aliases[k];
+    }
+    let k = k - aliases.len();
+    if k < eos.len() {
+        return (eos[k], ControlFlow::Eos);
+    }
+    let k = k - eos.len();
+    if k < patterns.len() {
+        return (patterns[k][0], ControlFlow::LeftParens(k as u8));
+    }
+    let k = k - patterns.len();
+    if k < patterns.len() {
+        return (patterns[k][1], ControlFlow::RightParens(k as u8));
+    }
+    match k - patterns.len() {
+        0 => ('\n', ControlFlow::LineBreaks),
+        _ => ('\r', ControlFlow::LineBreaks),
+    }
+}
+
+#[inline(always)]
+const fn slot(ch: char, seed: u32, bits: u32) -> usize {
+    ((ch as u32).wrapping_mul(seed) >> (32 - bits)) as usize
+}
+
+// Returns `(seed, bits)` such that distinct registered chars never share a slot.
+const fn layout(
+    eos: &[char],
+    patterns: &[[char; 2]],
+    aliases: &[(char, ControlFlow)],
+) -> (u32, u32) {
+    let n = num_entries(eos, patterns, aliases);
+    let mut bits = 1;
+    while (1 << bits) < n * 2 {
+        bits += 1;
+    }
+    loop {
+        let mut k = 0;
+        while k < SEEDS_PER_SIZE {
+            // odd multipliers spread around the golden ratio constant
+            let seed = 0x9E37_79B1u32.wrapping_add(k.wrapping_mul(0x7F4A_7C16)) | 1;
+            let mut collides = false;
+            let mut i = 0;
+            while i < n && !collides {
+                let a = entry(eos, patterns, aliases, i).0;
+                let mut j = i + 1;
+                while j < n && !collides {
+                    let b = entry(eos, patterns, aliases, j).0;
+                    collides = a != b && slot(a, seed, bits) == slot(b, seed, bits);
+                    j += 1;
+                }
+                i += 1;
+            }
+            if !collides {
+                return (seed, bits);
+            }
+            k += 1;
         }
+        bits += 1;
     }
 }
 
 impl CharTable {
-    #[inline(always)]
-    pub(crate) fn insert(&mut self, idx: char, value: ControlFlow) {
-        let idx = idx as usize;
-        if idx > self.max_idx {
-            self.table.extend(vec![None; idx - self.max_idx]);
-            self.max_idx = idx;
+    pub(crate) fn new(
+        eos: &[char],
+        patterns: &[[char; 2]],
+        aliases: &[(char, ControlFlow)],
+    ) -> Self {
+        let (seed, bits) = layout(eos, patterns, aliases);
+        let mut slots: Vec<Slot> = vec![None; 1 << bits];
+        for k in 0..num_entries(eos, patterns, aliases) {
+            let (ch, flow) = entry(eos, patterns, aliases, k);
+            slots[slot(ch, seed, bits)] = Some((ch, flow));
+        }
+        Self {
+            seed,
+            bits,
+            slots: Cow::Owned(slots),
         }
-        self.table[idx] = Some(value)
     }
+
     #[inline(always)]
     pub(crate) fn get(&self, idx: char) -> Option<&ControlFlow> {
-        let idx = idx as usize;
-        if idx > self.max_idx {
-            None
-        } else {
-            self.table[idx].as_ref()
+        match &self.slots[slot(idx, self.seed, self.bits)] {
+            Some((ch, flow)) if *ch == idx => Some(flow),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.slots.iter().flatten().map(|&(ch, _)| ch)
+    }
+}
+
+/// Returns the size parameter of the [`StaticTable`] for the given terminators and bracket patterns.
+pub const fn static_table_size(eos: &[char], patterns: &[[char; 2]]) -> usize {
+    1 << layout(eos, patterns, &[]).1
+}
+
+/// A char table laid out at compile time, used to build `'static` tokenizers with
+/// [`SentenceTokenizer::from_static`](crate::SentenceTokenizer::from_static).
+/// The [`tokenizer!`](crate::tokenizer) macro takes care of the size parameter.
+#[derive(Clone, Debug)]
+pub struct StaticTable<const N: usize> {
+    pub(crate) eos: &'static [char],
+    pub(crate) patterns: &'static [[char; 2]],
+    seed: u32,
+    bits: u32,
+    slots: [Slot; N],
+}
+
+impl<const N: usize> StaticTable<N> {
+    pub const fn new(eos: &'static [char], patterns: &'static [[char; 2]]) -> Self {
+        let (seed, bits) = layout(eos, patterns, &[]);
+        assert!(
+            N == 1 << bits,
+            "the size must be `static_table_size(eos, patterns)`"
+        );
+
+        let mut slots: [Slot; N] = [None; N];
+        let mut k = 0;
+        while k < num_entries(eos, patterns, &[]) {
+            let (ch, flow) = entry(eos, patterns, &[], k);
+            slots[slot(ch, seed, bits)] = Some((ch, flow));
+            k += 1;
         }
+        Self {
+            eos,
+            patterns,
+            seed,
+            bits,
+            slots,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn char_table(&'static self) -> CharTable {
+        CharTable {
+            seed: self.seed,
+            bits: self.bits,
+            slots: Cow::Borrowed(&self.slots),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CharTable, ControlFlow};
+
+    #[test]
+    fn stays_compact_for_distant_codepoints() {
+        let eos = ['。', '🔚'];
+        let patterns = [
+            ['（', '）'],
+            ['「', '」'],
+            ['『', '』'],
+            ['(', '\u{10FFFF}'],
+        ];
+        let table = CharTable::new(&eos, &patterns, &[]);
+
+        assert!(matches!(table.get('🔚'), Some(ControlFlow::Eos)));
+        assert!(matches!(table.get('「'), Some(ControlFlow::LeftParens(1))));
+        assert!(matches!(
+            table.get('\u{10FFFF}'),
+            Some(ControlFlow::RightParens(3))
+        ));
+        assert!(matches!(table.get('\r'), Some(ControlFlow::LineBreaks)));
+        assert!(table.get('あ').is_none());
+        assert!(table.get('\u{10FFFE}').is_none());
+        assert!(table.slots.len() <= 64);
     }
 }
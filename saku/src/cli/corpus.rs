@@ -0,0 +1,149 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::Instant,
+};
+
+const COMPRESSED_EXTENSIONS: [&str; 3] = ["gz", "zst", "xz"];
+
+// A file to tokenize. `relative` is its path below the directory it was found in, which is
+// mirrored in the output directory.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub path: PathBuf,
+    pub relative: PathBuf,
+}
+
+impl Input {
+    #[inline]
+    pub fn is_stdin(&self) -> bool {
+        self.path == Path::new("-")
+    }
+
+    // The output path of this input: the same relative path, without the compression extension.
+    pub fn output_path(&self, output_dir: &Path) -> PathBuf {
+        let mut path = output_dir.join(&self.relative);
+        if is_compressed(&path) {
+            path.set_extension("");
+        }
+        path
+    }
+}
+
+#[inline]
+fn is_compressed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| COMPRESSED_EXTENSIONS.contains(&ext))
+}
+
+// Expands directories into the files below them, in a stable order.
+pub fn collect_inputs(paths: &[PathBuf]) -> io::Result<Vec<Input>> {
+    let mut inputs = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, Path::new(""), &mut inputs)?;
+        } else {
+            let relative = path.file_name().map(PathBuf::from).unwrap_or_default();
+            inputs.push(Input {
+                path: path.clone(),
+                relative,
+            });
+        }
+    }
+    Ok(inputs)
+}
+
+fn walk(root: &Path, relative: &Path, inputs: &mut Vec<Input>) -> io::Result<()> {
+    let mut entries: Vec<fs::DirEntry> =
+        fs::read_dir(root.join(relative))?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let relative = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            walk(root, &relative, inputs)?;
+        } else {
+            inputs.push(Input {
+                path: root.join(&relative),
+                relative,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Opens an input, decompressing gzip, zstd and xz files by their extension.
+pub fn open(input: &Input) -> io::Result<Box<dyn Read>> {
+    if input.is_stdin() {
+        return Ok(Box::new(io::stdin()));
+    }
+    let file = BufReader::new(File::open(&input.path)?);
+    let extension = input.path.extension().and_then(|ext| ext.to_str());
+    Ok(match extension {
+        Some("gz") => Box::new(flate2::read::MultiGzDecoder::new(file)),
+        Some("zst") => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+        Some("xz") => Box::new(xz2::read::XzDecoder::new_multi_decoder(file)),
+        _ => Box::new(file),
+    })
+}
+
+pub fn read_to_end(input: &Input) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    open(input)?.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+// Counters shared by the workers, printed to stderr while running and at the end.
+pub struct Stats {
+    started: Instant,
+    total_files: usize,
+    files: AtomicUsize,
+    bytes: AtomicU64,
+    sentences: AtomicU64,
+    progress: bool,
+}
+
+impl Stats {
+    pub fn new(total_files: usize, progress: bool) -> Stats {
+        Stats {
+            started: Instant::now(),
+            total_files,
+            files: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            sentences: AtomicU64::new(0),
+            progress,
+        }
+    }
+
+    pub fn record(&self, bytes: usize, sentences: usize) {
+        self.files.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.sentences
+            .fetch_add(sentences as u64, Ordering::Relaxed);
+        if self.progress {
+            let _ = write!(io::stderr(), "\r{}", self.summary());
+        }
+    }
+
+    pub fn finish(&self) {
+        if self.progress {
+            let _ = writeln!(io::stderr(), "\r{}", self.summary());
+        }
+    }
+
+    fn summary(&self) -> String {
+        let seconds = self.started.elapsed().as_secs_f64();
+        let megabytes = self.bytes.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        format!(
+            "{}/{} files, {} sentences, {:.1} MB in {:.1}s ({:.1} MB/s)",
+            self.files.load(Ordering::Relaxed),
+            self.total_files,
+            self.sentences.load(Ordering::Relaxed),
+            megabytes,
+            seconds,
+            megabytes / seconds.max(f64::EPSILON),
+        )
+    }
+}
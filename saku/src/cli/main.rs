@@ -0,0 +1,547 @@
+mod corpus;
+
+use clap::{Parser, ValueEnum};
+use corpus::{Input, Stats};
+use rayon::prelude::*;
+use saku::{
+    read_jsonl, Decoded, Encoding, JsonlMode, JsonlOptions, LineBreaks, Markup, Normalization,
+    Preset, Provision, SentenceKind, SentenceTokenizer, SentenceTokenizerBuilder, Timing,
+    WhitespacePolicy, SPEECH_ENDINGS,
+};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Splits Japanese text into sentences, one sentence per line.
+#[derive(Debug, Parser)]
+#[command(name = "saku", version)]
+struct Args {
+    /// Input files or directories. Reads the standard input when none is given or for `-`.
+    /// Directories are read recursively, and `.gz`, `.zst` and `.xz` files are decompressed.
+    files: Vec<PathBuf>,
+
+    /// Writes to this file instead of the standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Writes the sentences of each input file to the same relative path in this directory.
+    #[arg(short = 'O', long, conflicts_with = "output")]
+    output_dir: Option<PathBuf>,
+
+    /// Number of files processed in parallel. Defaults to the number of CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Shows progress and throughput on the standard error.
+    #[arg(long)]
+    progress: bool,
+
+    /// Input encoding: utf-8, shift_jis, euc-jp or iso-2022-jp. Guessed for each file by default.
+    #[arg(short, long)]
+    encoding: Option<Encoding>,
+
+    /// Output format.
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Input format.
+    #[arg(short, long, value_enum, default_value_t = InputFormat::Text)]
+    input_format: InputFormat,
+
+    /// The field of JSONL records holding the text to tokenize.
+    #[arg(long, value_name = "NAME", default_value = "text")]
+    text_field: String,
+
+    /// The field of JSONL records holding the document id. Records without one are numbered.
+    #[arg(long, value_name = "NAME", default_value = "id")]
+    id_field: String,
+
+    /// The field for the index of a sentence in its document, with `--format jsonl`.
+    #[arg(long, value_name = "NAME", default_value = "index")]
+    index_field: String,
+
+    /// The field for the byte offset where a sentence starts, with `--input-format jsonl`.
+    #[arg(long, value_name = "NAME", default_value = "start")]
+    start_field: String,
+
+    /// The field for the byte offset where a sentence ends, with `--input-format jsonl`.
+    #[arg(long, value_name = "NAME", default_value = "end")]
+    end_field: String,
+
+    /// The field for the array of sentences, with `--format documents`.
+    #[arg(long, value_name = "NAME", default_value = "sentences")]
+    sentences_field: String,
+
+    /// Loads the tokenizer configuration from a TOML or JSON file.
+    #[arg(short, long, conflicts_with = "preset")]
+    config: Option<PathBuf>,
+
+    /// Starts from a preset: wikipedia, fiction, social-media, legal, academic,
+    /// simplified-chinese or traditional-chinese.
+    #[arg(short, long)]
+    preset: Option<Preset>,
+
+    /// Sentence terminators, e.g. `。！？`.
+    #[arg(long)]
+    eos: Option<String>,
+
+    /// A pair of brackets whose contents are never split, e.g. `「」`. Can be repeated.
+    #[arg(long = "pattern", value_name = "PAIR", value_parser = parse_pattern)]
+    patterns: Vec<[char; 2]>,
+
+    /// Disables bracket matching.
+    #[arg(long, conflicts_with = "patterns")]
+    no_patterns: bool,
+
+    /// How line breaks are treated.
+    #[arg(long, value_enum)]
+    line_breaks: Option<LineBreaksArg>,
+
+    /// Emits heading lines such as `第1章　はじめに` as sentences of their own.
+    #[arg(long)]
+    headings: bool,
+
+    /// Starts a sentence at each list marker that begins a line, such as `・`, `①` or `(1)`.
+    #[arg(long)]
+    list_items: bool,
+
+    /// Splits statutes and contracts at article, paragraph and item numbers such as `第一条`.
+    #[arg(long)]
+    legal: bool,
+
+    /// Markup of the input, removed before splitting.
+    #[arg(short, long, value_enum)]
+    markup: Option<MarkupArg>,
+
+    /// Keeps the ruby of Aozora Bunko texts instead of removing it.
+    #[arg(long)]
+    keep_ruby: bool,
+
+    /// Also ends sentences of unpunctuated speech after sentence-final forms such as `です` or
+    /// `ました` followed by whitespace or a word such as `それで`.
+    #[arg(long)]
+    speech: bool,
+
+    /// A sentence-final form for `--speech`, replacing the default ones. Can be repeated.
+    #[arg(long = "speech-ending", value_name = "ENDING", requires = "speech")]
+    speech_endings: Vec<String>,
+
+    /// Ends sentences of posts at emoji, kaomoji, laughter such as `（笑）` and ideographic spaces.
+    #[arg(long)]
+    social_media: bool,
+
+    /// Ends the sentence after a closing quote that follows a terminator, as in `“好。”`.
+    #[arg(long)]
+    quote_eos: bool,
+
+    /// Splits English and other Latin-script text with its own rules, keeping abbreviations,
+    /// initials and decimals.
+    #[arg(long)]
+    latin: bool,
+
+    /// Keeps runs of terminators such as `！？` in one sentence.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    glue_eos: Option<bool>,
+
+    /// Also recognizes the variants of terminators and brackets, e.g. `｡` for `。`.
+    #[arg(long, value_enum)]
+    normalization: Option<NormalizationArg>,
+
+    /// Trims the whitespace around each sentence.
+    #[arg(long)]
+    trim_sentences: bool,
+
+    /// Keeps the indentation of sentences that start a line.
+    #[arg(long)]
+    keep_indent: bool,
+
+    /// Keeps the whitespace around each document.
+    #[arg(long)]
+    no_trim_document: bool,
+
+    /// Does not treat U+3000 IDEOGRAPHIC SPACE as whitespace.
+    #[arg(long)]
+    keep_ideographic_space: bool,
+
+    /// Writes the resolved configuration to this TOML or JSON file.
+    #[arg(long, value_name = "PATH")]
+    save_config: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One sentence per line.
+    Text,
+    /// One JSON object per sentence, with its byte span in the input.
+    /// Sentences of non-UTF-8 input also have their char span in the decoded text.
+    /// With JSONL input, the fields of the document with its text replaced by the sentence.
+    Jsonl,
+    /// The JSONL input records with an added array of sentences.
+    Documents,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// Plain text documents.
+    Text,
+    /// One JSON object per line, with the document in a string field.
+    Jsonl,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum LineBreaksArg {
+    /// Joins lines and removes the line breaks.
+    Join,
+    /// Ends a sentence at every line break.
+    Split,
+    /// Joins soft-wrapped lines and keeps the other line breaks.
+    Auto,
+}
+
+impl From<LineBreaksArg> for LineBreaks {
+    fn from(arg: LineBreaksArg) -> LineBreaks {
+        match arg {
+            LineBreaksArg::Join => LineBreaks::Join,
+            LineBreaksArg::Split => LineBreaks::Split,
+            LineBreaksArg::Auto => LineBreaks::Auto,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum MarkupArg {
+    /// Plain text.
+    None,
+    /// Aozora Bunko texts with ruby, annotations, a header and a footer.
+    Aozora,
+    /// Markdown, of which only the prose is split.
+    Markdown,
+    /// HTML, with block-level tags ending sentences.
+    Html,
+    /// SRT or WebVTT subtitles, whose cues are joined into sentences.
+    Subtitles,
+    /// Transcripts with speaker labels such as `話者A：`.
+    Transcript,
+}
+
+impl From<MarkupArg> for Markup {
+    fn from(arg: MarkupArg) -> Markup {
+        match arg {
+            MarkupArg::None => Markup::None,
+            MarkupArg::Aozora => Markup::Aozora,
+            MarkupArg::Markdown => Markup::Markdown,
+            MarkupArg::Html => Markup::Html,
+            MarkupArg::Subtitles => Markup::Subtitles,
+            MarkupArg::Transcript => Markup::Transcript,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum NormalizationArg {
+    /// Only the registered chars.
+    None,
+    /// Half-width and full-width forms.
+    Width,
+    /// Single-char NFKC mappings, including vertical and small forms.
+    Nfkc,
+}
+
+impl From<NormalizationArg> for Normalization {
+    fn from(arg: NormalizationArg) -> Normalization {
+        match arg {
+            NormalizationArg::None => Normalization::None,
+            NormalizationArg::Width => Normalization::Width,
+            NormalizationArg::Nfkc => Normalization::Nfkc,
+        }
+    }
+}
+
+fn parse_pattern(pair: &str) -> Result<[char; 2], String> {
+    let chars: Vec<char> = pair.chars().collect();
+    match chars[..] {
+        [left, right] => Ok([left, right]),
+        _ => Err(format!("expected two characters, got {:?}", pair)),
+    }
+}
+
+impl Args {
+    fn builder(&self) -> Result<SentenceTokenizerBuilder, Box<dyn Error>> {
+        let mut builder = match (&self.config, self.preset) {
+            (Some(path), _) => SentenceTokenizerBuilder::from_config_file(path)?,
+            (None, Some(preset)) => SentenceTokenizerBuilder::preset(preset),
+            (None, None) => SentenceTokenizerBuilder::new(),
+        };
+        if let Some(eos) = &self.eos {
+            let eos: Vec<char> = eos.chars().collect();
+            builder = builder.eos_chars(&eos);
+        }
+        if self.no_patterns || !self.patterns.is_empty() {
+            builder = builder.patterns(&self.patterns);
+        }
+        if let Some(line_breaks) = self.line_breaks {
+            builder = builder.line_breaks(line_breaks.into());
+        }
+        if self.headings {
+            builder = builder.headings(true);
+        }
+        if self.list_items {
+            builder = builder.list_items(true);
+        }
+        if self.legal {
+            builder = builder.legal(true);
+        }
+        if let Some(markup) = self.markup {
+            builder = builder.markup(markup.into());
+        }
+        if self.keep_ruby {
+            builder = builder.keep_ruby(true);
+        }
+        if !self.speech_endings.is_empty() {
+            let endings: Vec<&str> = self.speech_endings.iter().map(String::as_str).collect();
+            builder = builder.speech_endings(&endings);
+        } else if self.speech {
+            builder = builder.speech_endings(&SPEECH_ENDINGS);
+        }
+        if self.social_media {
+            builder = builder.social_media(true);
+        }
+        if self.quote_eos {
+            builder = builder.quote_eos(true);
+        }
+        if self.latin {
+            builder = builder.latin(true);
+        }
+        if let Some(glue_eos) = self.glue_eos {
+            builder = builder.glue_eos(glue_eos);
+        }
+        if let Some(normalization) = self.normalization {
+            builder = builder.normalization(normalization.into());
+        }
+        let mut whitespace: WhitespacePolicy = builder.get_whitespace();
+        if self.trim_sentences {
+            whitespace = whitespace.trim_sentences(true);
+        }
+        if self.keep_indent {
+            whitespace = whitespace.keep_indent(true);
+        }
+        if self.no_trim_document {
+            whitespace = whitespace.trim_document(false);
+        }
+        if self.keep_ideographic_space {
+            whitespace = whitespace.ideographic_space(false);
+        }
+        builder = builder.whitespace(whitespace);
+        Ok(builder)
+    }
+
+    fn jsonl_options(&self) -> JsonlOptions {
+        let mode = match self.format {
+            Format::Documents => JsonlMode::Documents,
+            Format::Text | Format::Jsonl => JsonlMode::Sentences,
+        };
+        JsonlOptions::new()
+            .mode(mode)
+            .text_field(&self.text_field)
+            .id_field(&self.id_field)
+            .index_field(&self.index_field)
+            .start_field(&self.start_field)
+            .end_field(&self.end_field)
+            .sentences_field(&self.sentences_field)
+    }
+}
+
+#[derive(Serialize)]
+struct Record<'s> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'s Path>,
+    text: &'s str,
+    start: usize,
+    end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chars: Option<Range<usize>>,
+    #[serde(skip_serializing_if = "SentenceKind::is_text")]
+    kind: SentenceKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    marker: Option<&'s str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provision: Option<&'s Provision>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speaker: Option<&'s str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timing: Option<&'s Timing>,
+}
+
+fn write_records<'s, W: Write>(
+    out: &mut W,
+    format: Format,
+    records: impl Iterator<Item = Record<'s>>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+    for record in records {
+        match format {
+            Format::Text => writeln!(out, "{}", record.text)?,
+            Format::Jsonl | Format::Documents => {
+                serde_json::to_writer(&mut *out, &record)?;
+                writeln!(out)?;
+            }
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+// Tokenizes one input and returns the formatted output.
+fn process(
+    tokenizer: &SentenceTokenizer,
+    input: &Input,
+    args: &Args,
+    stats: &Stats,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let bytes =
+        corpus::read_to_end(input).map_err(|e| format!("{}: {}", input.path.display(), e))?;
+    let mut out = Vec::new();
+    if args.input_format == InputFormat::Jsonl {
+        let count = process_jsonl(tokenizer, &bytes, args, &mut out)
+            .map_err(|e| format!("{}: {}", input.path.display(), e))?;
+        stats.record(bytes.len(), count);
+        return Ok(out);
+    }
+    let encoding = args.encoding.unwrap_or_else(|| Encoding::detect(&bytes));
+    let file = Some(input.path.as_path()).filter(|_| !input.is_stdin());
+    let count = match std::str::from_utf8(&bytes) {
+        Ok(text) if encoding == Encoding::Utf8 => {
+            let sentences = tokenizer.tokenize_with_spans(text);
+            let records = sentences.iter().map(|sentence| Record {
+                file,
+                text: &sentence.text,
+                start: sentence.span.start,
+                end: sentence.span.end,
+                chars: None,
+                kind: sentence.kind,
+                marker: sentence.marker.clone().map(|marker| &text[marker]),
+                provision: sentence.provision.as_ref(),
+                speaker: sentence.speaker.as_deref(),
+                timing: sentence.timing.as_ref(),
+            });
+            write_records(&mut out, args.format, records)
+        }
+        _ => {
+            let decoded = Decoded::new(&bytes, encoding);
+            let sentences = tokenizer.tokenize_decoded(&decoded);
+            let records = sentences.iter().map(|sentence| Record {
+                file,
+                text: &sentence.text,
+                start: sentence.bytes.start,
+                end: sentence.bytes.end,
+                chars: Some(sentence.chars.clone()).filter(|_| encoding != Encoding::Utf8),
+                kind: sentence.kind,
+                marker: sentence.marker.as_deref(),
+                provision: sentence.provision.as_ref(),
+                speaker: sentence.speaker.as_deref(),
+                timing: sentence.timing.as_ref(),
+            });
+            write_records(&mut out, args.format, records)
+        }
+    }
+    .map_err(|e| e.to_string())?;
+    stats.record(bytes.len(), count);
+    Ok(out)
+}
+
+// Tokenizes JSONL records, writing JSONL or the bare sentences.
+fn process_jsonl(
+    tokenizer: &SentenceTokenizer,
+    bytes: &[u8],
+    args: &Args,
+    out: &mut Vec<u8>,
+) -> Result<usize, Box<dyn Error>> {
+    let options = args.jsonl_options();
+    if args.format != Format::Text {
+        return Ok(tokenizer.tokenize_jsonl(bytes, out, &options)?);
+    }
+    let mut count = 0;
+    for record in read_jsonl(bytes) {
+        let (line, record) = record?;
+        for sentence in tokenizer.tokenize_record(record, line, &options)? {
+            let text = sentence[options.get_text_field()]
+                .as_str()
+                .unwrap_or_default();
+            writeln!(out, "{}", text)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let builder = args.builder().map_err(|e| e.to_string())?;
+    if let Some(path) = &args.save_config {
+        builder.save_config_file(path)?;
+    }
+    let tokenizer: SentenceTokenizer = builder.build();
+    if args.format == Format::Documents && args.input_format != InputFormat::Jsonl {
+        return Err("`--format documents` needs `--input-format jsonl`".into());
+    }
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()?;
+    }
+
+    let files = if args.files.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        args.files.clone()
+    };
+    let inputs = corpus::collect_inputs(&files)?;
+    let stats = Stats::new(inputs.len(), args.progress);
+
+    if let Some(output_dir) = &args.output_dir {
+        inputs.par_iter().try_for_each(|input| {
+            let out = process(&tokenizer, input, &args, &stats)?;
+            let path = input.output_path(output_dir);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, out).map_err(|e| {
+                Box::<dyn Error + Send + Sync>::from(format!("{}: {}", path.display(), e))
+            })
+        })?;
+    } else {
+        let mut out: BufWriter<Box<dyn Write>> = BufWriter::new(match &args.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout().lock()),
+        });
+        // files are tokenized in parallel a chunk at a time and written in the input order
+        let chunk_size = rayon::current_num_threads() * 4;
+        for chunk in inputs.chunks(chunk_size) {
+            let outputs: Vec<Vec<u8>> = chunk
+                .par_iter()
+                .map(|input| process(&tokenizer, input, &args, &stats))
+                .collect::<Result<_, _>>()?;
+            for output in outputs {
+                out.write_all(&output)?;
+            }
+        }
+        out.flush()?;
+    }
+    stats.finish();
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("saku: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{SentenceTokenizer, SentenceTokenizerBuilder};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    string::String,
+};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    TomlDe(toml::de::Error),
+    TomlSer(toml::ser::Error),
+    Json(serde_json::Error),
+    UnknownFormat(PathBuf),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "failed to access the config file: {}", e),
+            ConfigError::TomlDe(e) => write!(f, "invalid TOML config: {}", e),
+            ConfigError::TomlSer(e) => write!(f, "failed to write the config as TOML: {}", e),
+            ConfigError::Json(e) => write!(f, "invalid JSON config: {}", e),
+            ConfigError::UnknownFormat(path) => write!(
+                f,
+                "unknown config format for {}, expected a .toml or .json file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            ConfigError::TomlDe(e) => Some(e),
+            ConfigError::TomlSer(e) => Some(e),
+            ConfigError::Json(e) => Some(e),
+            ConfigError::UnknownFormat(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    // Guesses the format from the file extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ConfigFormat, ConfigError> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("json") => Ok(ConfigFormat::Json),
+            _ => Err(ConfigError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+impl SentenceTokenizerBuilder {
+    pub fn from_config_str(config: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        match format {
+            ConfigFormat::Toml => toml::from_str(config).map_err(ConfigError::TomlDe),
+            ConfigFormat::Json => serde_json::from_str(config).map_err(ConfigError::Json),
+        }
+    }
+
+    pub fn to_config_string(&self, format: ConfigFormat) -> Result<String, ConfigError> {
+        match format {
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(ConfigError::TomlSer),
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(ConfigError::Json),
+        }
+    }
+
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let format = ConfigFormat::from_path(&path)?;
+        let config = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_config_str(&config, format)
+    }
+
+    pub fn save_config_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ConfigError> {
+        let format = ConfigFormat::from_path(&path)?;
+        let config = self.to_config_string(format)?;
+        fs::write(path, config).map_err(ConfigError::Io)
+    }
+}
+
+impl SentenceTokenizer {
+    #[inline]
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        SentenceTokenizerBuilder::from_config_file(path).map(|builder| builder.build())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{Provision, SentenceKind, SentenceTokenizer, Timing};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{fmt, ops::Range, str::FromStr};
+
+// Japanese text encodings read by `SentenceTokenizer::tokenize_bytes`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    Utf8,
+    ShiftJis,
+    EucJp,
+    Iso2022Jp,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 4] = [
+        Encoding::Utf8,
+        Encoding::ShiftJis,
+        Encoding::EucJp,
+        Encoding::Iso2022Jp,
+    ];
+
+    pub fn name(self) -> &'static str {
+        self.encoding_rs().name()
+    }
+
+    #[inline]
+    fn encoding_rs(self) -> &'static encoding_rs::Encoding {
+        match self {
+            Encoding::Utf8 => encoding_rs::UTF_8,
+            Encoding::ShiftJis => encoding_rs::SHIFT_JIS,
+            Encoding::EucJp => encoding_rs::EUC_JP,
+            Encoding::Iso2022Jp => encoding_rs::ISO_2022_JP,
+        }
+    }
+
+    // Guesses the encoding of a document.
+    // Valid UTF-8 wins, 7-bit text with JIS escape sequences is ISO-2022-JP, and otherwise the one
+    // of Shift_JIS and EUC-JP that decodes to more kana, kanji and ASCII is taken.
+    pub fn detect(bytes: &[u8]) -> Encoding {
+        if bytes.is_ascii() {
+            let jis = bytes
+                .windows(2)
+                .any(|w| w[0] == 0x1B && (w[1] == b'$' || w[1] == b'('));
+            return if jis {
+                Encoding::Iso2022Jp
+            } else {
+                Encoding::Utf8
+            };
+        }
+        if core::str::from_utf8(bytes).is_ok() {
+            return Encoding::Utf8;
+        }
+        let sjis = score(Encoding::ShiftJis, bytes);
+        let euc = score(Encoding::EucJp, bytes);
+        if euc > sjis {
+            Encoding::EucJp
+        } else {
+            Encoding::ShiftJis
+        }
+    }
+}
+
+// The number of chars typical of Japanese text when decoded with `encoding`.
+// Misdecoded bytes mostly turn into replacement chars, half-width katakana and rare symbols.
+fn score(encoding: Encoding, bytes: &[u8]) -> usize {
+    let (text, _) = encoding.encoding_rs().decode_without_bom_handling(bytes);
+    text.chars()
+        .filter(|&ch| {
+            ch.is_ascii()
+                || ('\u{3000}'..='\u{30FF}').contains(&ch)
+                || ('\u{4E00}'..='\u{9FFF}').contains(&ch)
+                || ('\u{FF01}'..='\u{FF5E}').contains(&ch)
+        })
+        .count()
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownEncoding;
+
+impl fmt::Display for UnknownEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown encoding, expected one of: ")?;
+        for (i, encoding) in Encoding::ALL.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(encoding.name())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownEncoding {}
+
+// Accepts the WHATWG labels of the encodings, such as `sjis`, `windows-31j` or `euc-jp`.
+impl FromStr for Encoding {
+    type Err = UnknownEncoding;
+
+    fn from_str(label: &str) -> Result<Encoding, UnknownEncoding> {
+        let encoding = encoding_rs::Encoding::for_label(label.as_bytes()).ok_or(UnknownEncoding)?;
+        Encoding::ALL
+            .into_iter()
+            .find(|e| e.encoding_rs() == encoding)
+            .ok_or(UnknownEncoding)
+    }
+}
+
+// A document decoded to UTF-8, which remembers where each char came from in the source bytes.
+#[derive(Clone, Debug)]
+pub struct Decoded {
+    pub encoding: Encoding,
+    pub text: String,
+    // (offset in `text`, offset in the source) at the start of every char, then at the end.
+    offsets: Vec<(usize, usize)>,
+}
+
+impl Decoded {
+    // Decodes `bytes`, replacing malformed sequences with U+FFFD.
+    // A UTF-8 byte order mark is skipped.
+    pub fn new(bytes: &[u8], encoding: Encoding) -> Decoded {
+        if encoding == Encoding::Utf8 {
+            let bom = if bytes.starts_with(b"\xEF\xBB\xBF") {
+                3
+            } else {
+                0
+            };
+            if let Ok(text) = core::str::from_utf8(&bytes[bom..]) {
+                let mut offsets: Vec<(usize, usize)> =
+                    text.char_indices().map(|(i, _)| (i, i + bom)).collect();
+                offsets.push((text.len(), bytes.len()));
+                return Decoded {
+                    encoding,
+                    text: String::from(text),
+                    offsets,
+                };
+            }
+        }
+
+        let mut decoder = encoding.encoding_rs().new_decoder_without_bom_handling();
+        let mut text = String::with_capacity(bytes.len() * 3 / 2);
+        let mut offsets: Vec<(usize, usize)> = Vec::with_capacity(bytes.len());
+        // the source offset of the first byte not yet decoded to a char
+        let mut start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            // ASCII bytes are themselves in the stateless encodings, so runs of them are decoded at
+            // once. A pending lead byte may take an ASCII byte as its trail, as in `表` (0x95 0x5C)
+            // in Shift_JIS, so the runs start only between chars.
+            if bytes[i].is_ascii() && start == i && encoding != Encoding::Iso2022Jp {
+                let ascii = bytes[i..]
+                    .iter()
+                    .position(|b| !b.is_ascii())
+                    .unwrap_or(bytes.len() - i);
+                let end = text.len();
+                decode(&mut decoder, &bytes[i..i + ascii], &mut text, false);
+                offsets.extend((0..ascii).map(|k| (end + k, i + k)));
+                i += ascii;
+                start = i;
+                continue;
+            }
+            // feeding one byte at a time tells which bytes every char was decoded from
+            let end = text.len();
+            decode(&mut decoder, &bytes[i..i + 1], &mut text, false);
+            i += 1;
+            push_chars(&mut offsets, &text, end, start, i - 1);
+            if text.len() > end {
+                start = i;
+            }
+        }
+        let end = text.len();
+        decode(&mut decoder, &[], &mut text, true);
+        push_chars(&mut offsets, &text, end, start, start);
+        offsets.push((text.len(), bytes.len()));
+        Decoded {
+            encoding,
+            text,
+            offsets,
+        }
+    }
+
+    // Decodes `bytes` in the encoding guessed by `Encoding::detect`.
+    #[inline]
+    pub fn detect(bytes: &[u8]) -> Decoded {
+        Decoded::new(bytes, Encoding::detect(bytes))
+    }
+
+    // The char index of a byte offset in `text`.
+    #[inline]
+    pub fn char_offset(&self, offset: usize) -> usize {
+        self.offsets.partition_point(|&(i, _)| i < offset)
+    }
+
+    // The source byte offset of a byte offset in `text`.
+    #[inline]
+    pub fn source_offset(&self, offset: usize) -> usize {
+        self.offsets[self.char_offset(offset)].1
+    }
+}
+
+#[inline]
+fn decode(decoder: &mut encoding_rs::Decoder, src: &[u8], text: &mut String, last: bool) {
+    if let Some(needed) = decoder.max_utf8_buffer_length(src.len()) {
+        text.reserve(needed);
+    }
+    let (result, _, _) = decoder.decode_to_string(src, text, last);
+    debug_assert_eq!(result, encoding_rs::CoderResult::InputEmpty);
+}
+
+// Records the chars decoded in one step. The first one started at `start`; any more (a replacement
+// char followed by the byte that ended the malformed sequence) at `last`.
+#[inline]
+fn push_chars(
+    offsets: &mut Vec<(usize, usize)>,
+    text: &str,
+    end: usize,
+    start: usize,
+    last: usize,
+) {
+    for (k, (i, _)) in text[end..].char_indices().enumerate() {
+        offsets.push((end + i, if k == 0 { start } else { last }));
+    }
+}
+
+// A sentence of a decoded document, located both in the decoded text and in the source bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DecodedSentence<'a> {
+    pub text: Cow<'a, str>,
+    // Char range of the sentence in the decoded text.
+    pub chars: Range<usize>,
+    // Byte range of the sentence in the source bytes.
+    pub bytes: Range<usize>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "SentenceKind::is_text")
+    )]
+    pub kind: SentenceKind,
+    // The marker of a list item, such as `・` or `(1)`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub marker: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub provision: Option<Provision>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub speaker: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub timing: Option<Timing>,
+}
+
+impl<'a> DecodedSentence<'a> {
+    #[inline]
+    pub fn into_owned(self) -> DecodedSentence<'static> {
+        DecodedSentence {
+            text: Cow::Owned(self.text.into_owned()),
+            chars: self.chars,
+            bytes: self.bytes,
+            kind: self.kind,
+            marker: self.marker.map(|marker| Cow::Owned(marker.into_owned())),
+            provision: self.provision,
+            speaker: self.speaker,
+            timing: self.timing,
+        }
+    }
+}
+
+impl SentenceTokenizer {
+    // Decodes a Shift_JIS, EUC-JP, ISO-2022-JP or UTF-8 document, guessing its encoding, and
+    // tokenizes it.
+    pub fn tokenize_bytes(&self, bytes: &[u8]) -> Vec<DecodedSentence<'static>> {
+        let decoded = Decoded::detect(bytes);
+        self.tokenize_decoded(&decoded)
+            .into_iter()
+            .map(DecodedSentence::into_owned)
+            .collect()
+    }
+
+    pub fn tokenize_decoded<'a>(&self, decoded: &'a Decoded) -> Vec<DecodedSentence<'a>> {
+        self.tokenize_with_spans(&decoded.text)
+            .into_iter()
+            .map(|sentence| {
+                let (start, end) = (sentence.span.start, sentence.span.end);
+                DecodedSentence {
+                    text: sentence.text,
+                    chars: decoded.char_offset(start)..decoded.char_offset(end),
+                    bytes: decoded.source_offset(start)..decoded.source_offset(end),
+                    kind: sentence.kind,
+                    marker: sentence
+                        .marker
+                        .map(|marker| Cow::Borrowed(&decoded.text[marker])),
+                    provision: sentence.provision,
+                    speaker: sentence.speaker,
+                    timing: sentence.timing,
+                }
+            })
+            .collect()
+    }
+}
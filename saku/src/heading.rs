@@ -0,0 +1,139 @@
+use crate::soft_wrap::{lines, width};
+use crate::{ControlFlow, SentenceTokenizer};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+// The widest heading in columns, with and without a numbering.
+const MAX_NUMBERED: usize = 60;
+const MAX_PLAIN: usize = 30;
+
+const KANJI_NUMERALS: &str = "〇一二三四五六七八九十百千零";
+// The counters of `第N章` and the like.
+const UNITS: &str = "章節部編項款条回話幕";
+const SYMBOLS: &str = "■□◆◇▼▽▲△★☆◎";
+
+#[inline(always)]
+pub(crate) fn is_digit(ch: char) -> bool {
+    ch.is_ascii_digit() || ('０'..='９').contains(&ch)
+}
+
+// The length of the numbering that starts `line`: `第1章`, `第十二節`, `1`, `1.2`, `２．３`
+// followed by a space, a symbol such as `■`, or a label such as `【お知らせ】`.
+fn numbering(line: &str) -> Option<usize> {
+    if let Some(rest) = line.strip_prefix('第') {
+        let number = rest.len()
+            - rest
+                .trim_start_matches(|ch| is_digit(ch) || KANJI_NUMERALS.contains(ch))
+                .len();
+        let unit = rest[number..]
+            .chars()
+            .next()
+            .filter(|&ch| UNITS.contains(ch));
+        return match unit {
+            Some(unit) if number > 0 => Some('第'.len_utf8() + number + unit.len_utf8()),
+            _ => None,
+        };
+    }
+    if let Some(symbol) = line.chars().next().filter(|&ch| SYMBOLS.contains(ch)) {
+        return Some(symbol.len_utf8());
+    }
+    if line.starts_with('【') {
+        return line.find('】').map(|i| i + '】'.len_utf8());
+    }
+    let mut len = 0;
+    loop {
+        let digits = line[len..].len() - line[len..].trim_start_matches(is_digit).len();
+        if digits == 0 {
+            break;
+        }
+        len += digits;
+        // `1.` alone is a list item, not a heading
+        match line[len..].chars().next() {
+            Some(dot @ ('.' | '．')) if line[len + dot.len_utf8()..].starts_with(is_digit) => {
+                len += dot.len_utf8()
+            }
+            _ => break,
+        }
+    }
+    Some(len).filter(|&len| len > 0 && line[len..].starts_with(char::is_whitespace))
+}
+
+impl SentenceTokenizer {
+    #[inline(always)]
+    fn is_eos(&self, ch: char) -> bool {
+        matches!(self.char_table.get(ch), Some(ControlFlow::Eos))
+    }
+
+    // Finds the heading lines of `document` and returns their byte ranges, without line breaks.
+    // A heading is a line followed by another line, with no terminator after its numbering, and
+    // short. A line without numbering must also open a paragraph: it comes after a blank line, a
+    // terminated line or the start of the document, and before a blank or indented line.
+    pub(crate) fn heading_lines(&self, document: &str) -> Vec<Range<usize>> {
+        let lines = lines(document);
+        let mut headings: Vec<Range<usize>> = Vec::new();
+        for (k, pair) in lines.windows(2).enumerate() {
+            let [(start, end), (next_start, next_end)] = [pair[0], pair[1]];
+            let line = document[start..end].trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (rest, max) = match numbering(line) {
+                Some(len) => (&line[len..], MAX_NUMBERED),
+                None => {
+                    let opens = k == 0 || {
+                        let (prev_start, prev_end) = lines[k - 1];
+                        let prev = document[prev_start..prev_end].trim_end();
+                        prev.is_empty()
+                            || headings.last().is_some_and(|last| last.start == prev_start)
+                            || prev.chars().next_back().is_some_and(|ch| {
+                                matches!(
+                                    self.char_table.get(ch),
+                                    Some(ControlFlow::Eos | ControlFlow::RightParens(_))
+                                )
+                            })
+                    };
+                    let next = &document[next_start..next_end];
+                    if !opens || !(next.trim().is_empty() || next.starts_with(char::is_whitespace))
+                    {
+                        continue;
+                    }
+                    (line, MAX_PLAIN)
+                }
+            };
+            if !rest.chars().any(|ch| self.is_eos(ch)) && width(line) <= max {
+                headings.push(start..end);
+            }
+        }
+        headings
+    }
+
+    // The line breaks around `headings`, which end the sentences before and after them.
+    pub(crate) fn heading_breaks(&self, document: &str, headings: &[Range<usize>]) -> Vec<usize> {
+        let mut hard: Vec<usize> = Vec::new();
+        for line in headings {
+            let before = document[..line.start].trim_end_matches(['\n', '\r']).len();
+            let after =
+                document.len() - document[line.end..].trim_start_matches(['\n', '\r']).len();
+            hard.extend(before..line.start);
+            hard.extend(line.end..after);
+        }
+        hard
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::numbering;
+
+    #[test]
+    fn numberings() {
+        assert_eq!(Some("第1章".len()), numbering("第1章　はじめに"));
+        assert_eq!(Some("第十二節".len()), numbering("第十二節 方法"));
+        assert_eq!(Some("1.2".len()), numbering("1.2 背景"));
+        assert_eq!(Some("■".len()), numbering("■概要"));
+        assert_eq!(Some("【お知らせ】".len()), numbering("【お知らせ】"));
+        assert_eq!(None, numbering("1. 項目"));
+        assert_eq!(None, numbering("第一に、"));
+        assert_eq!(None, numbering("2024年"));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::markup::Stripped;
+use alloc::string::String;
+
+// The elements that break the flow of text. Their tags, opening or closing, end sentences.
+const BLOCKS: [&str; 44] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "br",
+    "caption",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "option",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "td",
+    "th",
+    "title",
+    "tr",
+    "ul",
+    "tbody",
+];
+
+// The elements whose content is not text of the page. The title is metadata shown outside it.
+const SKIPPED: [&str; 7] = [
+    "script", "style", "template", "noscript", "svg", "math", "title",
+];
+
+// The named entities found in Japanese pages, besides the numeric ones.
+const ENTITIES: [(&str, char); 24] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{A0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("middot", '·'),
+    ("bull", '•'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("yen", '¥'),
+    ("deg", '°'),
+];
+
+// The decoded char of the entity starting at `start`, such as `&amp;` or `&#x3002;`, and the end
+// of the entity.
+pub(crate) fn entity(document: &str, start: usize) -> Option<(char, usize)> {
+    let rest = &document[start + 1..];
+    let len = rest.find(';').filter(|&len| len <= 32)?;
+    let name = &rest[..len];
+    let end = start + len + 2;
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return Some((char::from_u32(code).unwrap_or('\u{FFFD}'), end));
+    }
+    ENTITIES
+        .iter()
+        .find(|&&(entity, _)| entity == name)
+        .map(|&(_, ch)| (ch, end))
+}
+
+// The name of the tag starting at `start`, in lowercase, whether it is a closing tag, and the end
+// of the tag, after its `>`.
+fn tag(document: &str, start: usize) -> Option<(String, bool, usize)> {
+    let rest = &document[start + 1..];
+    let (closing, name_start) = match rest.strip_prefix('/') {
+        Some(_) => (true, 1),
+        None => (false, 0),
+    };
+    let name_len = rest[name_start..]
+        .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '-')
+        .unwrap_or(rest.len() - name_start);
+    let name = &rest[name_start..name_start + name_len];
+    if !name.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+    // the `>` outside quoted attribute values
+    let mut quote = None;
+    for (i, ch) in rest[name_start + name_len..].char_indices() {
+        match (ch, quote) {
+            ('"' | '\'', None) => quote = Some(ch),
+            (_, Some(q)) if ch == q => quote = None,
+            ('>', None) => {
+                let end = start + 1 + name_start + name_len + i + 1;
+                return Some((name.to_ascii_lowercase(), closing, end));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// The closing tag of the element `name` after `start`, matched case-insensitively, as `tag`
+// returns it.
+fn closing_tag(document: &str, start: usize, name: &str) -> Option<(String, bool, usize)> {
+    document[start..]
+        .match_indices("</")
+        .map(|(j, _)| start + j)
+        .filter(|&j| {
+            document.as_bytes()[j + 2..]
+                .get(..name.len())
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(name.as_bytes()))
+        })
+        .find_map(|j| tag(document, j).filter(|(found, _, _)| found == name))
+}
+
+// Removes the markup of an HTML document. Text is kept, with its entities decoded and its runs of
+// whitespace collapsed, and block-level tags are replaced with line breaks that end sentences.
+// Comments, declarations, the title and the content of scripts and styles are left out.
+pub(crate) fn strip(document: &str) -> Stripped {
+    let mut stripped = Stripped::new(document.len());
+    // the start of the text being copied
+    let mut run = 0;
+    let mut i = 0;
+    // where the text of the open heading starts
+    let mut heading: Option<usize> = None;
+    // the depth of `<pre>` elements, in which whitespace is kept
+    let mut pre: usize = 0;
+    let bytes = document.as_bytes();
+
+    while i < document.len() {
+        match bytes[i] {
+            b'<' => {
+                let markup = if document[i..].starts_with("<!--") {
+                    document[i..].find("-->").map(|j| (None, i + j + 3))
+                } else if document[i..].starts_with("<!") || document[i..].starts_with("<?") {
+                    document[i..].find('>').map(|j| (None, i + j + 1))
+                } else {
+                    tag(document, i).map(|(name, closing, end)| (Some((name, closing)), end))
+                };
+                if let Some((element, mut end)) = markup {
+                    stripped.push(document, run..i);
+                    if let Some((name, closing)) = element {
+                        let name = name.as_str();
+                        if !closing && SKIPPED.contains(&name) {
+                            // the content, up to the closing tag
+                            end = closing_tag(document, end, name)
+                                .map_or(document.len(), |(_, _, close_end)| close_end);
+                        }
+                        if name == "pre" {
+                            pre = if closing {
+                                pre.saturating_sub(1)
+                            } else {
+                                pre + 1
+                            };
+                        }
+                        let is_heading = matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6");
+                        if BLOCKS.contains(&name) {
+                            if let Some(start) = heading.take().filter(|_| closing) {
+                                stripped.heading(start);
+                            }
+                            stripped.push_break(i..end);
+                            if is_heading && !closing {
+                                heading = Some(stripped.text.len());
+                            }
+                        }
+                    }
+                    run = end;
+                    i = end;
+                    continue;
+                }
+            }
+            b'&' => {
+                if let Some((ch, end)) = entity(document, i) {
+                    stripped.push(document, run..i);
+                    stripped.push_replaced(ch.encode_utf8(&mut [0; 4]), i..end);
+                    run = end;
+                    i = end;
+                    continue;
+                }
+            }
+            b' ' | b'\t' | b'\n' | b'\r' if pre == 0 => {
+                let len = document[i..].len()
+                    - document[i..]
+                        .trim_start_matches([' ', '\t', '\n', '\r'])
+                        .len();
+                stripped.push(document, run..i);
+                // a single space or line break, so that joined lines are spaced as in plain text,
+                // and none at the start of a block
+                let block_start = stripped.text.is_empty() || stripped.text.ends_with('\n');
+                if !block_start {
+                    let kept = document[i..i + len].find('\n').map_or(i, |j| i + j);
+                    stripped.push(document, kept..kept + 1);
+                }
+                run = i + len;
+                i = run;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    stripped.push(document, run..document.len());
+    stripped
+}
+
+#[cfg(test)]
+mod test {
+    use super::strip;
+
+    #[test]
+    fn strips_tags() {
+        let document = "<html><head><title>題</title><style>p { color: red; }</style></head>\n<body>\n  <p>一文目。<b>二文目</b>&amp;&#x3002;</p>\n</body></html>";
+        let stripped = strip(document);
+        let lines: alloc::vec::Vec<&str> = stripped
+            .text
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .collect();
+        let text = lines.join("|");
+        assert_eq!("一文目。二文目&。", text);
+
+        let stripped = strip("<SCRIPT>a = \"</scripts>\";</Script>本文。");
+        assert_eq!("本文。", stripped.text.trim());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::SentenceTokenizer;
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+// What `SentenceTokenizer::tokenize_jsonl` writes for each input record.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsonlMode {
+    // One record per sentence: the fields of the document with its text replaced by the sentence,
+    // plus the sentence index and its byte span in the text.
+    #[default]
+    Sentences,
+    // The document record with an added array of sentences.
+    Documents,
+}
+
+// Field names of the JSONL records. Fields other than these are copied as they are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonlOptions {
+    mode: JsonlMode,
+    text_field: String,
+    id_field: String,
+    index_field: String,
+    start_field: String,
+    end_field: String,
+    sentences_field: String,
+}
+
+impl Default for JsonlOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonlOptions {
+    pub fn new() -> Self {
+        Self {
+            mode: JsonlMode::Sentences,
+            text_field: "text".to_string(),
+            id_field: "id".to_string(),
+            index_field: "index".to_string(),
+            start_field: "start".to_string(),
+            end_field: "end".to_string(),
+            sentences_field: "sentences".to_string(),
+        }
+    }
+
+    pub fn mode(self, mode: JsonlMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    // The field holding the document to tokenize.
+    pub fn text_field(self, text_field: &str) -> Self {
+        Self {
+            text_field: text_field.to_string(),
+            ..self
+        }
+    }
+
+    // The document id. Records without one are numbered by their line, starting from 0.
+    pub fn id_field(self, id_field: &str) -> Self {
+        Self {
+            id_field: id_field.to_string(),
+            ..self
+        }
+    }
+
+    // The index of a sentence in its document, in `JsonlMode::Sentences`.
+    pub fn index_field(self, index_field: &str) -> Self {
+        Self {
+            index_field: index_field.to_string(),
+            ..self
+        }
+    }
+
+    // The byte offset where a sentence starts in the text, in `JsonlMode::Sentences`.
+    pub fn start_field(self, start_field: &str) -> Self {
+        Self {
+            start_field: start_field.to_string(),
+            ..self
+        }
+    }
+
+    // The byte offset where a sentence ends in the text, in `JsonlMode::Sentences`.
+    pub fn end_field(self, end_field: &str) -> Self {
+        Self {
+            end_field: end_field.to_string(),
+            ..self
+        }
+    }
+
+    // The array of sentences added in `JsonlMode::Documents`.
+    pub fn sentences_field(self, sentences_field: &str) -> Self {
+        Self {
+            sentences_field: sentences_field.to_string(),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn get_mode(&self) -> JsonlMode {
+        self.mode
+    }
+
+    #[inline]
+    pub fn get_text_field(&self) -> &str {
+        &self.text_field
+    }
+}
+
+#[derive(Debug)]
+pub enum JsonlError {
+    Io(io::Error),
+    // A line that is not a JSON object, with its line number starting from 1.
+    Json(usize, serde_json::Error),
+    // A record that could not be written, with the line number of its input starting from 1.
+    Serialize(usize, serde_json::Error),
+    NotAnObject(usize),
+    // A record whose text field is missing or not a string.
+    MissingText(usize, String),
+}
+
+impl fmt::Display for JsonlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonlError::Io(e) => write!(f, "failed to read or write records: {}", e),
+            JsonlError::Json(line, e) => write!(f, "line {}: invalid JSON: {}", line, e),
+            JsonlError::Serialize(line, e) => {
+                write!(f, "line {}: failed to serialize the record: {}", line, e)
+            }
+            JsonlError::NotAnObject(line) => write!(f, "line {}: not a JSON object", line),
+            JsonlError::MissingText(line, field) => {
+                write!(f, "line {}: no string field {:?}", line, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonlError::Io(e) => Some(e),
+            JsonlError::Json(_, e) | JsonlError::Serialize(_, e) => Some(e),
+            JsonlError::NotAnObject(_) | JsonlError::MissingText(..) => None,
+        }
+    }
+}
+
+// Reads JSONL records with their 0-based line numbers. Blank lines are skipped.
+pub fn read_jsonl<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<(usize, Map<String, Value>), JsonlError>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| text.as_ref().map_or(true, |text| !text.trim().is_empty()))
+        .map(|(line, text)| {
+            let text = text.map_err(JsonlError::Io)?;
+            match serde_json::from_str(&text) {
+                Ok(Value::Object(record)) => Ok((line, record)),
+                Ok(_) => Err(JsonlError::NotAnObject(line + 1)),
+                Err(e) => Err(JsonlError::Json(line + 1, e)),
+            }
+        })
+}
+
+impl SentenceTokenizer {
+    // Tokenizes the text field of one record, where `line` is its 0-based line number.
+    // Returns the records to write: one per sentence or the updated document.
+    pub fn tokenize_record(
+        &self,
+        mut record: Map<String, Value>,
+        line: usize,
+        options: &JsonlOptions,
+    ) -> Result<Vec<Map<String, Value>>, JsonlError> {
+        let text = match record.get(&options.text_field) {
+            Some(Value::String(text)) => text.clone(),
+            _ => {
+                return Err(JsonlError::MissingText(
+                    line + 1,
+                    options.text_field.clone(),
+                ))
+            }
+        };
+        let sentences = self.tokenize_with_spans(&text);
+
+        match options.mode {
+            JsonlMode::Sentences => {
+                if !record.contains_key(&options.id_field) {
+                    record.insert(options.id_field.clone(), Value::from(line));
+                }
+                let records = sentences
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, sentence)| {
+                        let mut record = record.clone();
+                        record.insert(
+                            options.text_field.clone(),
+                            Value::from(sentence.text.into_owned()),
+                        );
+                        record.insert(options.index_field.clone(), Value::from(index));
+                        record.insert(
+                            options.start_field.clone(),
+                            Value::from(sentence.span.start),
+                        );
+                        record.insert(options.end_field.clone(), Value::from(sentence.span.end));
+                        record
+                    })
+                    .collect();
+                Ok(records)
+            }
+            JsonlMode::Documents => {
+                let sentences = serde_json::to_value(sentences)
+                    .map_err(|e| JsonlError::Serialize(line + 1, e))?;
+                record.insert(options.sentences_field.clone(), sentences);
+                Ok(vec![record])
+            }
+        }
+    }
+
+    // Reads JSONL records, tokenizes them and writes the results as JSONL.
+    // Blank lines are skipped. Returns the number of sentences.
+    pub fn tokenize_jsonl<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+        options: &JsonlOptions,
+    ) -> Result<usize, JsonlError> {
+        let mut count = 0;
+        for record in read_jsonl(reader) {
+            let (line, record) = record?;
+            let records = self.tokenize_record(record, line, options)?;
+            count += match options.mode {
+                JsonlMode::Sentences => records.len(),
+                JsonlMode::Documents => records[0][&options.sentences_field]
+                    .as_array()
+                    .map_or(0, Vec::len),
+            };
+            for record in records {
+                serde_json::to_writer(&mut writer, &record)
+                    .map_err(|e| JsonlError::Serialize(line + 1, e))?;
+                writer.write_all(b"\n").map_err(JsonlError::Io)?;
+            }
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::SentenceTokenizer;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+// The words shortened with a dot that do not end sentences, as in `Dr. Smith`.
+const ABBREVIATIONS: [&str; 40] = [
+    "Mr", "Mrs", "Ms", "Dr", "Prof", "Sr", "Jr", "St", "Mt", "Gen", "Gov", "Sen", "Rep", "Rev",
+    "Capt", "Lt", "Col", "Sgt", "vs", "cf", "al", "Fig", "fig", "No", "no", "Vol", "vol", "pp",
+    "approx", "Jan", "Feb", "Mar", "Apr", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[inline(always)]
+fn is_terminator(ch: char) -> bool {
+    matches!(ch, '.' | '?' | '!' | '…')
+}
+
+#[inline(always)]
+fn is_closer(ch: char) -> bool {
+    matches!(ch, '"' | '\'' | ')' | ']' | '”' | '’')
+}
+
+#[inline(always)]
+fn is_opener(ch: char) -> bool {
+    matches!(ch, '"' | '\'' | '(' | '[' | '“' | '‘')
+}
+
+// Letters and digits of Latin script.
+#[inline(always)]
+fn is_latin(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || (ch.is_alphabetic() && ('\u{C0}'..='\u{24F}').contains(&ch))
+}
+
+// Text of other scripts, such as Japanese, which starts a sentence after Latin text.
+#[inline(always)]
+fn is_other_script(ch: char) -> bool {
+    ch.is_alphanumeric() && !is_latin(ch)
+}
+
+// Whether the dot at `dot` shortens the word before it: an abbreviation such as `Dr`, an initial
+// such as `J` or a dotted acronym such as `U.S` or `e.g`.
+fn is_abbreviation(document: &str, dot: usize) -> bool {
+    let before = &document[..dot];
+    let word = &before[before
+        .trim_end_matches(|ch: char| is_latin(ch) || ch == '.')
+        .len()..];
+    ABBREVIATIONS.contains(&word)
+        || word.contains('.')
+        || (word.chars().count() == 1 && word.chars().all(|ch| ch.is_uppercase()))
+}
+
+impl SentenceTokenizer {
+    // Finds the ends of the sentences of Latin-script text, such as English paragraphs in Japanese
+    // documents: a `.`, `?`, `!` or `…` after a Latin word, with the closing quotes after it,
+    // followed by whitespace and an uppercase letter, an opening quote or text of another script,
+    // or directly by text of another script. Abbreviations, initials and decimals end nothing.
+    // Returns the ends with the ranges of the terminators after Latin words, whose own rules are
+    // ignored.
+    pub(crate) fn latin_ends(&self, document: &str) -> (Vec<usize>, Vec<Range<usize>>) {
+        let mut ends: Vec<usize> = Vec::new();
+        let mut terminators: Vec<Range<usize>> = Vec::new();
+        let mut i = 0;
+        while let Some(offset) = document[i..].find(is_terminator) {
+            let start = i + offset;
+            let rest = &document[start..];
+            let run = start + rest.len() - rest.trim_start_matches(is_terminator).len();
+            i = run;
+            let previous = document[..start].chars().next_back();
+            if !previous.is_some_and(|ch| is_latin(ch) || is_closer(ch)) {
+                continue;
+            }
+            terminators.push(start..run);
+            let end =
+                run + document[run..].len() - document[run..].trim_start_matches(is_closer).len();
+            let after = &document[end..];
+            let spaced = after.trim_start();
+            let next = spaced.chars().next();
+            let ends_sentence = if spaced.len() < after.len() {
+                next.is_some_and(|ch| ch.is_uppercase() || is_opener(ch) || is_other_script(ch))
+            } else {
+                next.is_some_and(is_other_script)
+            };
+            let shortened = &document[start..run] == "." && is_abbreviation(document, start);
+            if ends_sentence && !shortened {
+                ends.push(end);
+            }
+        }
+        (ends, terminators)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::SentenceTokenizerBuilder;
+
+    #[test]
+    fn ends() {
+        let tokenizer = SentenceTokenizerBuilder::new().latin(true).build();
+        let document =
+            "Dr. Smith paid $3.50 for it, e.g. a pen. \"Why?\" he asked... Then he left.これは";
+        let (ends, _) = tokenizer.latin_ends(document);
+        let ends: alloc::vec::Vec<&str> = ends.into_iter().map(|end| &document[..end]).collect();
+        assert_eq!(
+            alloc::vec![
+                "Dr. Smith paid $3.50 for it, e.g. a pen.",
+                "Dr. Smith paid $3.50 for it, e.g. a pen. \"Why?\" he asked...",
+                "Dr. Smith paid $3.50 for it, e.g. a pen. \"Why?\" he asked... Then he left.",
+            ],
+            ends
+        );
+    }
+}
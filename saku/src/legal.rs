@@ -0,0 +1,182 @@
+use crate::heading::is_digit;
+use crate::soft_wrap::lines;
+use crate::{LineBreaks, SentenceTokenizer};
+use alloc::{string::String, vec::Vec};
+
+// The position of a sentence in the structure of a statute or contract, found with
+// `SentenceTokenizerBuilder::legal`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Provision {
+    // The article, e.g. `第一条` or `第十二条の二`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub article: Option<String>,
+    // The caption of the article, e.g. `目的` for `（目的）`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub caption: Option<String>,
+    // The paragraph number, 1 for the unnumbered first paragraph of an article.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub paragraph: Option<u32>,
+    // The item number, e.g. 3 for `三`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub item: Option<u32>,
+}
+
+enum Marker {
+    Article(usize),
+    Paragraph(u32),
+    Item(u32),
+}
+
+const KANJI_DIGITS: &str = "〇一二三四五六七八九";
+
+#[inline(always)]
+fn is_numeral(ch: char) -> bool {
+    is_digit(ch) || KANJI_DIGITS.contains(ch) || "十百千".contains(ch)
+}
+
+#[inline(always)]
+fn digit_value(ch: char) -> Option<u32> {
+    match ch {
+        '０'..='９' => Some(ch as u32 - '０' as u32),
+        _ => ch
+            .to_digit(10)
+            .or_else(|| KANJI_DIGITS.chars().position(|d| d == ch).map(|d| d as u32)),
+    }
+}
+
+// The value of a number written in digits or kanji, e.g. `１２`, `十二`, `百五` or `二〇二四`.
+fn parse_number(number: &str) -> Option<u32> {
+    if !number.contains(['十', '百', '千']) {
+        return number.chars().try_fold(0u32, |n, ch| {
+            n.checked_mul(10)?.checked_add(digit_value(ch)?)
+        });
+    }
+    let (mut total, mut digit) = (0, None);
+    for ch in number.chars() {
+        let unit = match ch {
+            '十' => 10,
+            '百' => 100,
+            '千' => 1000,
+            _ => {
+                digit = Some(digit_value(ch)?);
+                continue;
+            }
+        };
+        total += digit.take().unwrap_or(1) * unit;
+    }
+    Some(total + digit.unwrap_or(0))
+}
+
+// The length of the number that starts `text`.
+#[inline(always)]
+fn number_len(text: &str) -> usize {
+    text.len() - text.trim_start_matches(is_numeral).len()
+}
+
+// The structure marker that starts `line`: `第一条`, `第十二条の二`, a paragraph number such as
+// `２` or an item number such as `一`, followed by a space or the end of the line.
+fn marker(line: &str) -> Option<Marker> {
+    let spaced =
+        |len: usize| line[len..].is_empty() || line[len..].starts_with(char::is_whitespace);
+    if let Some(rest) = line.strip_prefix('第') {
+        let mut len = '第'.len_utf8() + number_len(rest);
+        if len == '第'.len_utf8() || !line[len..].starts_with('条') {
+            return None;
+        }
+        len += '条'.len_utf8();
+        while let Some(branch) = line[len..].strip_prefix('の') {
+            match number_len(branch) {
+                0 => break,
+                number => len += 'の'.len_utf8() + number,
+            }
+        }
+        return Some(Marker::Article(len)).filter(|_| spaced(len));
+    }
+    let len = number_len(line);
+    if len == 0 || !spaced(len) {
+        return None;
+    }
+    let number = parse_number(&line[..len])?;
+    if line[..len].chars().all(is_digit) {
+        Some(Marker::Paragraph(number))
+    } else {
+        Some(Marker::Item(number))
+    }
+}
+
+// The caption of a line such as `（目的）`.
+fn caption_of(line: &str) -> Option<&str> {
+    line.strip_prefix(['（', '('])?
+        .strip_suffix(['）', ')'])
+        .filter(|caption| !caption.is_empty())
+}
+
+impl SentenceTokenizer {
+    // Finds the articles, paragraphs and items of `document`, and returns the line breaks that end
+    // sentences with the provisions and the positions they start at.
+    // A line break is kept before each marker, and in the split mode before every line but an
+    // article under a caption: the caption, such as `（目的）`, stays with its article.
+    pub(crate) fn provisions(&self, document: &str) -> (Vec<usize>, Vec<(usize, Provision)>) {
+        let lines = lines(document);
+        let mut hard: Vec<usize> = Vec::new();
+        let mut provisions: Vec<(usize, Provision)> = Vec::new();
+        let mut current = Provision::default();
+        // the start and the text of a caption on the previous line
+        let mut captioned: Option<(usize, &str)> = None;
+
+        for (k, &(start, end)) in lines.iter().enumerate() {
+            let line = document[start..end].trim();
+            let marker = marker(line);
+            let caption = captioned
+                .take()
+                .filter(|_| matches!(marker, Some(Marker::Article(_))));
+            if k > 0
+                && caption.is_none()
+                && (marker.is_some() || self.line_breaks == LineBreaks::Split)
+            {
+                // the line break, with the `\r` of a `\r\n`
+                hard.extend(lines[k - 1].1..start);
+            }
+            if let Some((caption_start, _)) = caption.filter(|_| k > 1) {
+                hard.extend(lines[k - 2].1..caption_start);
+            }
+            match marker {
+                Some(Marker::Article(len)) => {
+                    current = Provision {
+                        article: Some(String::from(&line[..len])),
+                        caption: caption.map(|(_, caption)| String::from(caption)),
+                        paragraph: Some(1),
+                        item: None,
+                    };
+                }
+                Some(Marker::Paragraph(paragraph)) => {
+                    current.paragraph = Some(paragraph);
+                    current.item = None;
+                }
+                Some(Marker::Item(item)) => current.item = Some(item),
+                None => {
+                    captioned = caption_of(line).map(|caption| (start, caption));
+                    continue;
+                }
+            }
+            let start = caption.map_or(start, |(start, _)| start);
+            provisions.push((start, current.clone()));
+        }
+        (hard, provisions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_number;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Some(12), parse_number("１２"));
+        assert_eq!(Some(3), parse_number("三"));
+        assert_eq!(Some(12), parse_number("十二"));
+        assert_eq!(Some(105), parse_number("百五"));
+        assert_eq!(Some(2024), parse_number("二〇二四"));
+    }
+}
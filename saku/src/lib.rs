@@ -1,8 +1,38 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod aozora;
+mod batch;
 mod builder;
 mod char_table;
+#[cfg(feature = "serde")]
+mod config;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod heading;
+mod html;
+#[cfg(feature = "serde")]
+mod jsonl;
+mod latin;
+mod legal;
+mod list;
+mod markdown;
+mod markup;
+mod normalize;
+mod preset;
+mod scanner;
+mod sentence;
+mod social;
+mod soft_wrap;
+mod speech;
+mod subtitles;
 mod tokenizer;
+mod whitespace;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub(crate) enum ControlFlow {
     Eos,
     LineBreaks,
@@ -10,6 +40,45 @@ pub(crate) enum ControlFlow {
     LeftParens(u8),
 }
 pub(crate) use char_table::CharTable;
+pub(crate) use scanner::LazyScanner;
 
-pub use builder::SentenceTokenizerBuilder;
+pub use builder::{LineBreaks, SentenceTokenizerBuilder};
+pub use char_table::{static_table_size, StaticTable};
+#[cfg(feature = "serde")]
+pub use config::{ConfigError, ConfigFormat};
+#[cfg(feature = "encoding")]
+pub use encoding::{Decoded, DecodedSentence, Encoding, UnknownEncoding};
+#[cfg(feature = "serde")]
+pub use jsonl::{read_jsonl, JsonlError, JsonlMode, JsonlOptions};
+pub use legal::Provision;
+pub use markup::Markup;
+pub use normalize::Normalization;
+pub use preset::{Preset, UnknownPreset};
+pub use sentence::{Sentence, SentenceKind};
+pub use speech::SPEECH_ENDINGS;
+pub use subtitles::Timing;
 pub use tokenizer::SentenceTokenizer;
+pub use whitespace::WhitespacePolicy;
+
+/// Builds a [`SentenceTokenizer`] whose tables are laid out at compile time.
+/// The expansion is a constant expression, so it can initialize a `static` without any runtime setup.
+///
+/// ```
+/// static TOKENIZER: saku::SentenceTokenizer =
+///     saku::tokenizer!(eos = ['。', '！'], pairs = [('「', '」'), ('（', '）')]);
+///
+/// assert_eq!(
+///     vec!["「はい！」と答えた。", "本当に！", "すごい。"],
+///     TOKENIZER.tokenize("「はい！」と答えた。本当に！すごい。"),
+/// );
+/// ```
+#[macro_export]
+macro_rules! tokenizer {
+    (eos = [$($eos:expr),* $(,)?], pairs = [$(($left:expr, $right:expr)),* $(,)?] $(,)?) => {{
+        const EOS: &[char] = &[$($eos),*];
+        const PATTERNS: &[[char; 2]] = &[$([$left, $right]),*];
+        const TABLE: $crate::StaticTable<{ $crate::static_table_size(EOS, PATTERNS) }> =
+            $crate::StaticTable::new(EOS, PATTERNS);
+        $crate::SentenceTokenizer::from_static(&TABLE)
+    }};
+}
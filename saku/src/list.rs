@@ -0,0 +1,101 @@
+use crate::heading::is_digit;
+use crate::soft_wrap::lines;
+use crate::SentenceTokenizer;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+const BULLETS: &str = "・･•‣◦●○";
+
+// Circled and parenthesized numbers: `①`, `㉑`, `⑴` and `⒈`.
+#[inline(always)]
+fn is_enclosed_number(ch: char) -> bool {
+    matches!(ch, '\u{2460}'..='\u{249B}' | '\u{3251}'..='\u{325F}' | '\u{32B1}'..='\u{32BF}')
+}
+
+// The length of the list marker that starts `line`: a bullet such as `・`, an enclosed number
+// such as `①`, a number or letter in parentheses such as `(1)`, `（一）` or `(a)`, or a number
+// followed by `)` or a dot, such as `1)` or `1.`.
+fn marker(line: &str) -> Option<usize> {
+    let first = line.chars().next()?;
+    if BULLETS.contains(first) || is_enclosed_number(first) {
+        return Some(first.len_utf8());
+    }
+    let (open, rest) = match first {
+        '(' | '（' => (first.len_utf8(), &line[first.len_utf8()..]),
+        _ => (0, line),
+    };
+    let label = rest.len()
+        - rest
+            .trim_start_matches(|ch| is_digit(ch) || "一二三四五六七八九十".contains(ch))
+            .len();
+    let label = match label {
+        // a single letter, only in parentheses
+        0 if open > 0 => rest
+            .chars()
+            .next()
+            .filter(char::is_ascii_lowercase)
+            .map_or(0, char::len_utf8),
+        _ => label,
+    };
+    if label == 0 {
+        return None;
+    }
+    let len = open + label;
+    match line[len..].chars().next()? {
+        close @ (')' | '）') => Some(len + close.len_utf8()),
+        // `1.5` is a number, not a marker
+        dot @ ('.' | '．') if open == 0 && !line[len + dot.len_utf8()..].starts_with(is_digit) => {
+            Some(len + dot.len_utf8())
+        }
+        _ => None,
+    }
+}
+
+impl SentenceTokenizer {
+    // Finds the list markers at the start of the lines of `document`, after any indentation, and
+    // returns their byte ranges.
+    pub(crate) fn list_markers(&self, document: &str) -> Vec<Range<usize>> {
+        lines(document)
+            .into_iter()
+            .filter_map(|(start, end)| {
+                let line = &document[start..end];
+                let indent = line.len() - line.trim_start().len();
+                let len = marker(&line[indent..])?;
+                // the marker must be followed by the item
+                Some(start + indent..start + indent + len)
+                    .filter(|_| !line[indent + len..].trim().is_empty())
+            })
+            .collect()
+    }
+
+    // The line breaks before the lines starting with `markers`, which end the previous item.
+    pub(crate) fn list_breaks(&self, document: &str, markers: &[Range<usize>]) -> Vec<usize> {
+        let mut hard: Vec<usize> = Vec::new();
+        for marker in markers {
+            let line_start = document[..marker.start].rfind('\n').map_or(0, |i| i + 1);
+            let before = document[..line_start].trim_end_matches(['\n', '\r']).len();
+            hard.extend(before..line_start);
+        }
+        hard
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::marker;
+
+    #[test]
+    fn markers() {
+        assert_eq!(Some("・".len()), marker("・項目A"));
+        assert_eq!(Some("①".len()), marker("①最初に"));
+        assert_eq!(Some("(1)".len()), marker("(1) 概要"));
+        assert_eq!(Some("（一）".len()), marker("（一）総則"));
+        assert_eq!(Some("(a)".len()), marker("(a) first"));
+        assert_eq!(Some("1.".len()), marker("1. 項目"));
+        assert_eq!(Some("１．".len()), marker("１．項目"));
+        assert_eq!(Some("2)".len()), marker("2) 項目"));
+        assert_eq!(None, marker("1.5倍になった。"));
+        assert_eq!(None, marker("(注)"));
+        assert_eq!(None, marker("2024年"));
+    }
+}
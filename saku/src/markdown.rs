@@ -0,0 +1,402 @@
+use crate::markup::Stripped;
+use crate::soft_wrap::lines;
+use alloc::vec::Vec;
+
+// The length of the leading spaces of `line`, at most `max`.
+#[inline(always)]
+fn indent(line: &str, max: usize) -> usize {
+    line.bytes().take(max).take_while(|&b| b == b' ').count()
+}
+
+// The end of the `>` markers of block quotes that start `line`.
+fn quote_prefix(line: &str) -> usize {
+    let mut len = 0;
+    loop {
+        let rest = &line[len..];
+        let spaces = indent(rest, 3);
+        if !rest[spaces..].starts_with('>') {
+            return len;
+        }
+        len += spaces + 1;
+        if line[len..].starts_with(' ') {
+            len += 1;
+        }
+    }
+}
+
+// The char and the length of the code fence that starts `line`, such as ```` ``` ```` or `~~~`.
+fn fence(line: &str) -> Option<(char, usize)> {
+    let line = &line[indent(line, 3)..];
+    let ch = line.chars().next().filter(|&ch| ch == '`' || ch == '~')?;
+    let len = line.len() - line.trim_start_matches(ch).len();
+    Some((ch, len)).filter(|_| len >= 3)
+}
+
+// A line of three or more `-`, `*` or `_`, possibly spaced.
+fn is_thematic_break(line: &str) -> bool {
+    let line = line.trim();
+    let mut chars = line.chars().filter(|&ch| ch != ' ' && ch != '\t');
+    match chars.next() {
+        Some(first @ ('-' | '*' | '_')) => {
+            chars.clone().all(|ch| ch == first) && chars.count() >= 2
+        }
+        _ => false,
+    }
+}
+
+// The length of the `#` marker of an ATX heading and its space.
+fn heading_marker(line: &str) -> Option<usize> {
+    let start = indent(line, 3);
+    let hashes = line[start..].len() - line[start..].trim_start_matches('#').len();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[start + hashes..];
+    if rest.is_empty() {
+        Some(start + hashes)
+    } else if rest.starts_with([' ', '\t']) {
+        Some(start + hashes + 1)
+    } else {
+        None
+    }
+}
+
+// The length of the list marker that starts `line`, such as `- `, `* ` or `1. `, with its space.
+fn list_marker(line: &str) -> Option<usize> {
+    let start = indent(line, 3);
+    let rest = &line[start..];
+    let digits = rest.len()
+        - rest
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    let len = match rest[digits..].chars().next()? {
+        '-' | '*' | '+' if digits == 0 => 1,
+        '.' | ')' if (1..=9).contains(&digits) => digits + 1,
+        _ => return None,
+    };
+    match rest[len..].chars().next() {
+        Some(' ' | '\t') => Some(start + len + 1),
+        None => Some(start + len),
+        _ => None,
+    }
+}
+
+// A table delimiter row such as `| --- | :-: |`.
+fn is_delimiter_row(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('-')
+        && line.contains(['|', ':'])
+        && line
+            .chars()
+            .all(|ch| matches!(ch, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+// The end of the code span whose opening backticks start at `start`, after the closing ones.
+fn code_span_end(text: &str, start: usize) -> Option<usize> {
+    let ticks = text[start..].len() - text[start..].trim_start_matches('`').len();
+    let mut i = start + ticks;
+    while let Some(j) = text[i..].find('`') {
+        let run = text[i + j..].len() - text[i + j..].trim_start_matches('`').len();
+        if run == ticks {
+            return Some(i + j + run);
+        }
+        i += j + run;
+    }
+    None
+}
+
+// The end of the bracketed text starting at `start`, after its `]`.
+fn bracket_end(text: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, ch) in text[start..].char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// Copies the inline content `document[start..end]` to `stripped`: code spans, autolinks and bare
+// URLs are kept whole, the text of links and images is kept without their destination, and
+// backslash escapes are removed.
+fn inline(stripped: &mut Stripped, document: &str, start: usize, end: usize) {
+    let text = &document[..end];
+    let mut run = start;
+    let mut i = start;
+    while i < end {
+        let rest = &text[i..];
+        let ch = rest.chars().next().unwrap_or_default();
+        match ch {
+            '\\' if rest[1..].starts_with(|ch: char| ch.is_ascii_punctuation()) => {
+                stripped.push(document, run..i);
+                run = i + 1;
+                i += 2;
+                continue;
+            }
+            '`' => {
+                if let Some(code_end) = code_span_end(text, i) {
+                    stripped.push(document, run..i);
+                    stripped.push_opaque(document, i..code_end);
+                    run = code_end;
+                    i = code_end;
+                    continue;
+                }
+                i += rest.len() - rest.trim_start_matches('`').len();
+                continue;
+            }
+            '<' if rest.starts_with("<http://") || rest.starts_with("<https://") => {
+                if let Some(close) = rest.find('>').filter(|&j| !rest[..j].contains(' ')) {
+                    stripped.push(document, run..i);
+                    stripped.push_opaque(document, i..i + close + 1);
+                    run = i + close + 1;
+                    i = run;
+                    continue;
+                }
+            }
+            'h' if rest.starts_with("http://") || rest.starts_with("https://") => {
+                let len = rest
+                    .find(|ch: char| !ch.is_ascii_graphic() || ch == '<')
+                    .unwrap_or(rest.len());
+                // a dot or a bracket ending the URL belongs to the sentence
+                let len = rest[..len].trim_end_matches(['.', ',', ')', ']']).len();
+                stripped.push(document, run..i);
+                stripped.push_opaque(document, i..i + len);
+                run = i + len;
+                i = run;
+                continue;
+            }
+            '[' | '!' => {
+                let open = if ch == '!' { i + 1 } else { i };
+                if text[open..].starts_with('[') {
+                    if let Some(close) = bracket_end(text, open) {
+                        // `[text](url)`, `[text][label]` or `[text][]`
+                        let destination = match text[close..].chars().next() {
+                            Some('(') => text[close..].find(')').map(|j| close + j + 1),
+                            Some('[') => bracket_end(text, close),
+                            _ => None,
+                        };
+                        if let Some(destination) = destination {
+                            stripped.push(document, run..i);
+                            inline(stripped, document, open + 1, close - 1);
+                            run = destination;
+                            i = destination;
+                            continue;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += ch.len_utf8();
+    }
+    stripped.push(document, run..end);
+}
+
+// The cells of the table row `document[start..end]`, without the outer pipes.
+fn table_cells(document: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let row = &document[start..end];
+    let mut cells = Vec::new();
+    let mut cell = start;
+    let mut i = 0;
+    while i < row.len() {
+        match row.as_bytes()[i] {
+            b'\\' => i += 1,
+            b'`' => {
+                if let Some(code_end) = code_span_end(row, i) {
+                    i = code_end;
+                    continue;
+                }
+            }
+            b'|' => {
+                cells.push((cell, start + i));
+                cell = start + i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    cells.push((cell, end));
+    // the pipes at both ends do not delimit cells
+    if row.trim_start().starts_with('|') {
+        cells.remove(0);
+    }
+    if row.trim_end().ends_with('|') && !row.trim_end().ends_with("\\|") {
+        cells.pop();
+    }
+    cells
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Block {
+    None,
+    Paragraph,
+    List,
+    Table,
+    // a fenced code block, with its fence
+    Fenced(char, usize),
+}
+
+// Removes the markup of a Markdown document, keeping the prose of paragraphs, headings, list
+// items, block quotes and table cells. Code blocks, thematic breaks, HTML blocks and the front
+// matter are left out, and every block ends its sentences.
+pub(crate) fn strip(document: &str) -> Stripped {
+    let lines = lines(document);
+    let mut stripped = Stripped::new(document.len());
+    let mut block = Block::None;
+    // where the text of the current paragraph starts, for setext headings
+    let mut paragraph = 0;
+    let mut in_list = false;
+
+    let mut k = 0;
+    // YAML front matter
+    if lines.len() > 1 && document[lines[0].0..lines[0].1].trim_end() == "---" {
+        if let Some(n) = lines[1..]
+            .iter()
+            .position(|&(start, end)| matches!(document[start..end].trim_end(), "---" | "..."))
+        {
+            k = n + 2;
+        }
+    }
+
+    while k < lines.len() {
+        let (line_start, line_end) = lines[k];
+        let next_start = lines.get(k + 1).map_or(document.len(), |&(start, _)| start);
+        k += 1;
+        let line = &document[line_start..line_end];
+        let quote = quote_prefix(line);
+        let start = line_start + quote;
+        let content = &document[start..line_end];
+
+        if let Block::Fenced(ch, len) = block {
+            if fence(content).is_some_and(|(c, n)| c == ch && n >= len)
+                && content
+                    .trim_start()
+                    .trim_start_matches(ch)
+                    .trim()
+                    .is_empty()
+            {
+                block = Block::None;
+            }
+            continue;
+        }
+        if content.trim().is_empty() {
+            stripped.end_block();
+            block = Block::None;
+            continue;
+        }
+        if let Some((ch, len)) = fence(content) {
+            stripped.end_block();
+            block = Block::Fenced(ch, len);
+            continue;
+        }
+        // the underline of a setext heading, after the lines of the heading
+        let underline = content.trim();
+        if block == Block::Paragraph
+            && (underline.chars().all(|ch| ch == '=') || underline.chars().all(|ch| ch == '-'))
+        {
+            stripped.heading(paragraph);
+            stripped.end_block();
+            block = Block::None;
+            continue;
+        }
+        if is_thematic_break(content) {
+            stripped.end_block();
+            block = Block::None;
+            continue;
+        }
+        let indented = content.starts_with("    ") || content.starts_with('\t');
+        if block == Block::None && indented && !in_list {
+            // indented code
+            continue;
+        }
+        let html = content.trim_start().strip_prefix('<').is_some_and(|tag| {
+            tag.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '/' || ch == '!')
+        });
+        if block == Block::None && html {
+            continue;
+        }
+        if let Some(marker) = heading_marker(content) {
+            stripped.end_block();
+            let text = content[marker..].trim_end();
+            // the closing sequence of `## heading ##`
+            let text = match text.trim_end_matches('#') {
+                closed if closed.is_empty() || closed.ends_with([' ', '\t']) => closed.trim_end(),
+                _ => text,
+            };
+            let heading = stripped.text.len();
+            inline(
+                &mut stripped,
+                document,
+                start + marker,
+                start + marker + text.len(),
+            );
+            stripped.heading(heading);
+            stripped.push(document, line_end..next_start);
+            stripped.end_block();
+            block = Block::None;
+            continue;
+        }
+        let table_row = content.contains('|')
+            && (block == Block::Table
+                || lines
+                    .get(k)
+                    .is_some_and(|&(start, end)| is_delimiter_row(&document[start..end])));
+        if table_row {
+            stripped.end_block();
+            for (cell_start, cell_end) in table_cells(document, start, line_end) {
+                let cell = &document[cell_start..cell_end];
+                let leading = cell.len() - cell.trim_start().len();
+                inline(
+                    &mut stripped,
+                    document,
+                    cell_start + leading,
+                    cell_start + cell.trim_end().len(),
+                );
+                if cell_end < line_end {
+                    stripped.push_break(cell_end..cell_end + 1);
+                }
+            }
+            stripped.push(document, line_end..next_start);
+            stripped.end_block();
+            if block != Block::Table {
+                // the delimiter row
+                k += 1;
+            }
+            block = Block::Table;
+            continue;
+        }
+        if block == Block::Table {
+            block = Block::None;
+        }
+        let text_start = match list_marker(content) {
+            Some(marker) => {
+                stripped.end_block();
+                block = Block::List;
+                in_list = true;
+                start + marker
+            }
+            None => {
+                if block == Block::None {
+                    block = Block::Paragraph;
+                    paragraph = stripped.text.len();
+                    // a paragraph inside a list item is indented
+                    in_list &= content.starts_with([' ', '\t']);
+                }
+                start + content.len() - content.trim_start().len()
+            }
+        };
+        inline(&mut stripped, document, text_start, line_end);
+        stripped.push(document, line_end..next_start);
+    }
+    stripped
+}
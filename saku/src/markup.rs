@@ -0,0 +1,228 @@
+use crate::{Sentence, SentenceKind, Timing};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::ops::Range;
+
+// The markup of the documents. Markup is removed before splitting, so that it never decides a
+// boundary, and the spans of the sentences are mapped back to the document with its markup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[non_exhaustive]
+pub enum Markup {
+    // Plain text.
+    #[default]
+    None,
+    // Aozora Bunko texts, with ruby such as `｜漢字《かんじ》`, annotations such as `［＃改ページ］`,
+    // and the header and footer around the work.
+    Aozora,
+    // Markdown: only the prose of paragraphs, headings, list items, block quotes and table cells is
+    // split, code is kept whole, and link URLs are removed.
+    Markdown,
+    // HTML: entities are decoded, scripts and styles skipped, block-level tags such as `<p>`,
+    // `<br>` and `<li>` end sentences, and inline tags are removed.
+    Html,
+    // SRT or WebVTT subtitles: cue numbers, timings and tags are removed, the text of the cues is
+    // joined, and each sentence records the cues it covers and its speaker.
+    Subtitles,
+    // Transcripts whose turns start with a speaker label such as `話者A：`, possibly after a
+    // timestamp such as `[00:01:23]`.
+    Transcript,
+}
+
+// The text of a document with its markup removed, made of runs copied from the document and of
+// replacements, such as decoded entities.
+pub(crate) struct Stripped {
+    pub(crate) text: String,
+    // (offset in `text`, offset in the document, length in the document of a replacement) at the
+    // start of every run
+    runs: Vec<(usize, usize, Option<usize>)>,
+    // the line breaks of `text` that end blocks, and thus sentences
+    pub(crate) breaks: Vec<usize>,
+    // the ranges of `text` whose control chars are ignored, such as code
+    pub(crate) opaque: Vec<Range<usize>>,
+    headings: Vec<Range<usize>>,
+    // (range of `text`, start, end) of every cue, with times in milliseconds
+    cues: Vec<(Range<usize>, u64, u64)>,
+    // the offsets of `text` where the speaker changes, with the new speaker
+    speakers: Vec<(usize, Option<String>)>,
+}
+
+impl Stripped {
+    #[inline]
+    pub(crate) fn new(capacity: usize) -> Stripped {
+        Stripped {
+            text: String::with_capacity(capacity),
+            runs: Vec::new(),
+            breaks: Vec::new(),
+            opaque: Vec::new(),
+            headings: Vec::new(),
+            cues: Vec::new(),
+            speakers: Vec::new(),
+        }
+    }
+
+    // Copies `document[range]` to the end of the text.
+    #[inline]
+    pub(crate) fn push(&mut self, document: &str, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        let continued = self.runs.last().is_some_and(|&(i, j, replaced)| {
+            replaced.is_none() && j + self.text.len() - i == range.start
+        });
+        if !continued {
+            self.runs.push((self.text.len(), range.start, None));
+        }
+        self.text.push_str(&document[range]);
+    }
+
+    // Adds `text` in place of `document[source]`, such as `&` for `&amp;`.
+    #[inline]
+    pub(crate) fn push_replaced(&mut self, text: &str, source: Range<usize>) {
+        if text.is_empty() {
+            return;
+        }
+        self.runs
+            .push((self.text.len(), source.start, Some(source.len())));
+        self.text.push_str(text);
+    }
+
+    // Copies `document[range]` to the end of the text, and makes its control chars ignored.
+    #[inline]
+    pub(crate) fn push_opaque(&mut self, document: &str, range: Range<usize>) {
+        let start = self.text.len();
+        self.push(document, range);
+        if self.text.len() > start {
+            self.opaque.push(start..self.text.len());
+        }
+    }
+
+    // Adds a line break that ends a sentence in place of `document[source]`, such as the `|`
+    // between two table cells or a `<br>` tag.
+    #[inline]
+    pub(crate) fn push_break(&mut self, source: Range<usize>) {
+        self.breaks.push(self.text.len());
+        self.push_replaced("\n", source);
+    }
+
+    // Ends the current block at the line break that ends the text, if any.
+    #[inline]
+    pub(crate) fn end_block(&mut self) {
+        let trailing = self.text.len() - self.text.trim_end_matches(['\n', '\r']).len();
+        let start = self.text.len() - trailing;
+        for i in start..self.text.len() {
+            if self.breaks.last().is_none_or(|&last| last < i) {
+                self.breaks.push(i);
+            }
+        }
+    }
+
+    // Marks the text from `start` to the end as a heading.
+    #[inline]
+    pub(crate) fn heading(&mut self, start: usize) {
+        let end = self.text.trim_end().len();
+        if end > start {
+            self.headings.push(start..end);
+        }
+    }
+
+    // Marks the text from `start` to the end as a cue of subtitles, from `start_time` to
+    // `end_time`.
+    #[inline]
+    pub(crate) fn cue(&mut self, start: usize, start_time: u64, end_time: u64) {
+        let end = self.text.trim_end().len().max(start);
+        self.cues.push((start..end, start_time, end_time));
+    }
+
+    // Sets the speaker of the text that follows. A change of speaker ends the sentence, at the
+    // line break that ends the text or in place of `document[source]`.
+    pub(crate) fn speaker(&mut self, speaker: Option<&str>, source: Range<usize>) {
+        let current = self
+            .speakers
+            .last()
+            .and_then(|(_, speaker)| speaker.as_deref());
+        if current == speaker {
+            return;
+        }
+        if self.text.ends_with('\n') {
+            self.end_block();
+        } else if !self.text.is_empty() {
+            self.push_break(source);
+        }
+        self.speakers
+            .push((self.text.len(), speaker.map(String::from)));
+    }
+
+    // The cues that a range of the text overlaps.
+    fn timing(&self, range: &Range<usize>) -> Option<Timing> {
+        let first = self
+            .cues
+            .partition_point(|(cue, _, _)| cue.end <= range.start);
+        let last = self
+            .cues
+            .partition_point(|(cue, _, _)| cue.start < range.end);
+        if first >= last {
+            return None;
+        }
+        Some(Timing {
+            cues: first..last,
+            start: self.cues[first].1,
+            end: self.cues[last - 1].2,
+        })
+    }
+
+    // Maps a range of the text to the document. The start goes with the run that follows it, and
+    // the end with the run before it, so that the markup around a range is left out.
+    // A range covers either the whole or none of a replacement.
+    #[inline]
+    fn source_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = match self.runs.partition_point(|&(i, _, _)| i <= range.start) {
+            0 => range.start,
+            k => match self.runs[k - 1] {
+                (i, j, None) => j + range.start - i,
+                (i, j, Some(len)) if range.start > i => j + len,
+                (_, j, Some(_)) => j,
+            },
+        };
+        let end = match self.runs.partition_point(|&(i, _, _)| i < range.end) {
+            0 => start,
+            k => match self.runs[k - 1] {
+                (i, j, None) => j + range.end - i,
+                (_, j, Some(len)) => j + len,
+            },
+        };
+        start..end.max(start)
+    }
+
+    // Moves a sentence of the text to the document.
+    pub(crate) fn restore<'a>(&self, sentence: Sentence<'_>) -> Sentence<'a> {
+        let heading = self.headings.iter().any(|heading| {
+            heading.start <= sentence.span.start && sentence.span.end <= heading.end
+        });
+        let speaker = match self
+            .speakers
+            .partition_point(|&(i, _)| i <= sentence.span.start)
+        {
+            0 => None,
+            k => self.speakers[k - 1].1.clone(),
+        };
+        let timing = self.timing(&sentence.span);
+        Sentence {
+            text: Cow::Owned(sentence.text.into_owned()),
+            span: self.source_range(sentence.span),
+            inserted: sentence.inserted,
+            kind: if heading {
+                SentenceKind::Heading
+            } else {
+                sentence.kind
+            },
+            marker: sentence.marker.map(|marker| self.source_range(marker)),
+            provision: sentence.provision,
+            speaker,
+            timing,
+        }
+    }
+}
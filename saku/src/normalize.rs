@@ -0,0 +1,178 @@
+use crate::ControlFlow;
+use alloc::vec::Vec;
+
+// Which variants of the registered chars are recognized as the same control char.
+// Normalization is only used to find boundaries: the registered chars gain aliases in the char
+// table, so the document itself is never rewritten and sentences and spans refer to it as is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Normalization {
+    #[default]
+    None,
+    // Half-width and full-width forms are equal, e.g. `｡` and `。`, or `(` and `（`.
+    Width,
+    // The single-char NFKC mappings, which also fold the vertical presentation forms such as `︒`
+    // and the small forms such as `﹖`.
+    Nfkc,
+}
+
+// Half-width CJK punctuation, and the full-width brackets outside the ASCII block.
+const WIDTH_FOLDS: [(char, char); 8] = [
+    ('\u{3000}', ' '),
+    ('\u{FF5F}', '⦅'),
+    ('\u{FF60}', '⦆'),
+    ('｡', '。'),
+    ('｢', '「'),
+    ('｣', '」'),
+    ('､', '、'),
+    ('･', '・'),
+];
+
+// Vertical forms, CJK compatibility forms and small form variants.
+const NFKC_FOLDS: [(char, char); 59] = [
+    ('︐', ','),
+    ('︑', '、'),
+    ('︒', '。'),
+    ('︓', ':'),
+    ('︔', ';'),
+    ('︕', '!'),
+    ('︖', '?'),
+    ('︗', '〖'),
+    ('︘', '〗'),
+    ('︙', '…'),
+    ('︰', '‥'),
+    ('︱', '—'),
+    ('︲', '–'),
+    ('︳', '_'),
+    ('︴', '_'),
+    ('︵', '('),
+    ('︶', ')'),
+    ('︷', '{'),
+    ('︸', '}'),
+    ('︹', '〔'),
+    ('︺', '〕'),
+    ('︻', '【'),
+    ('︼', '】'),
+    ('︽', '《'),
+    ('︾', '》'),
+    ('︿', '〈'),
+    ('﹀', '〉'),
+    ('﹁', '「'),
+    ('﹂', '」'),
+    ('﹃', '『'),
+    ('﹄', '』'),
+    ('﹇', '['),
+    ('﹈', ']'),
+    ('﹐', ','),
+    ('﹑', '、'),
+    ('﹒', '.'),
+    ('﹔', ';'),
+    ('﹕', ':'),
+    ('﹖', '?'),
+    ('﹗', '!'),
+    ('﹘', '—'),
+    ('﹙', '('),
+    ('﹚', ')'),
+    ('﹛', '{'),
+    ('﹜', '}'),
+    ('﹝', '〔'),
+    ('﹞', '〕'),
+    ('﹟', '#'),
+    ('﹠', '&'),
+    ('﹡', '*'),
+    ('﹢', '+'),
+    ('﹣', '-'),
+    ('﹤', '<'),
+    ('﹥', '>'),
+    ('﹦', '='),
+    ('﹨', '\\'),
+    ('﹩', '$'),
+    ('﹪', '%'),
+    ('﹫', '@'),
+];
+
+impl Normalization {
+    #[inline]
+    fn fold(self, ch: char) -> char {
+        if self == Normalization::None {
+            return ch;
+        }
+        // full-width ASCII
+        if ('！'..='～').contains(&ch) {
+            return char::from_u32(ch as u32 - 0xFEE0).unwrap_or(ch);
+        }
+        let folds: &[(char, char)] = match self {
+            Normalization::Nfkc if ('\u{FE10}'..='\u{FE6B}').contains(&ch) => &NFKC_FOLDS,
+            _ => &WIDTH_FOLDS,
+        };
+        folds
+            .iter()
+            .find(|&&(from, _)| from == ch)
+            .map_or(ch, |&(_, to)| to)
+    }
+
+    // Every char with a fold of its own: ASCII, the full-width forms and the tables.
+    fn variants(self) -> impl Iterator<Item = char> {
+        let nfkc: &[(char, char)] = match self {
+            Normalization::Nfkc => &NFKC_FOLDS,
+            _ => &[],
+        };
+        ('!'..='~').chain('！'..='～').chain(
+            WIDTH_FOLDS
+                .iter()
+                .chain(nfkc)
+                .flat_map(|&(from, to)| [from, to]),
+        )
+    }
+
+    // The char table entries for the variants of the registered chars.
+    pub(crate) fn aliases(self, eos: &[char], patterns: &[[char; 2]]) -> Vec<(char, ControlFlow)> {
+        if self == Normalization::None {
+            return Vec::new();
+        }
+        let registered: Vec<(char, ControlFlow)> = eos
+            .iter()
+            .map(|&ch| (ch, ControlFlow::Eos))
+            .chain(patterns.iter().enumerate().flat_map(|(k, &[left, right])| {
+                [
+                    (left, ControlFlow::LeftParens(k as u8)),
+                    (right, ControlFlow::RightParens(k as u8)),
+                ]
+            }))
+            .collect();
+        let mut aliases: Vec<(char, ControlFlow)> = Vec::new();
+        for variant in self.variants() {
+            let folded = self.fold(variant);
+            let flow = registered
+                .iter()
+                .find(|&&(ch, _)| ch != variant && self.fold(ch) == folded);
+            if let Some(&(_, flow)) = flow {
+                if aliases.iter().all(|&(ch, _)| ch != variant) {
+                    aliases.push((variant, flow));
+                }
+            }
+        }
+        aliases
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ControlFlow, Normalization};
+
+    #[test]
+    fn width_aliases() {
+        let aliases = Normalization::Width.aliases(&['。'], &[['（', '）']]);
+        let chars: alloc::vec::Vec<char> = aliases.iter().map(|&(ch, _)| ch).collect();
+        assert_eq!(alloc::vec!['(', ')', '｡'], chars);
+        assert!(matches!(aliases[0].1, ControlFlow::LeftParens(0)));
+
+        let aliases = Normalization::Nfkc.aliases(&['。'], &[]);
+        assert!(aliases.iter().any(|&(ch, _)| ch == '︒'));
+        assert!(Normalization::None.aliases(&['。'], &[]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{LineBreaks, Normalization, SentenceTokenizer, SentenceTokenizerBuilder};
+use core::{fmt, str::FromStr};
+
+// Ready-made configurations for common kinds of Japanese text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Preset {
+    // Encyclopedic prose: readings in （）, quotes in 「」, one paragraph per line.
+    Wikipedia,
+    // Novels: dialogue lines in 「」『』, ！？ ending sentences, one paragraph per line.
+    Fiction,
+    // Posts and chats: full-width and ASCII ！？ runs, emoji, kaomoji and laughter, one post per line.
+    SocialMedia,
+    // Statutes and contracts: numbered articles, paragraphs and items on their own lines.
+    Legal,
+    // Papers written with ．and ，instead of 。and 、, wrapped at arbitrary points.
+    Academic,
+    // Simplified Chinese: 。！？；, quotes in “”‘’ and titles in 《》, ending a sentence when a
+    // quote closes after a terminator, and half-width punctuation mixed in.
+    SimplifiedChinese,
+    // Traditional Chinese: as simplified Chinese, with quotes in 「」『』 first.
+    TraditionalChinese,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 7] = [
+        Preset::Wikipedia,
+        Preset::Fiction,
+        Preset::SocialMedia,
+        Preset::Legal,
+        Preset::Academic,
+        Preset::SimplifiedChinese,
+        Preset::TraditionalChinese,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Wikipedia => "wikipedia",
+            Preset::Fiction => "fiction",
+            Preset::SocialMedia => "social-media",
+            Preset::Legal => "legal",
+            Preset::Academic => "academic",
+            Preset::SimplifiedChinese => "simplified-chinese",
+            Preset::TraditionalChinese => "traditional-chinese",
+        }
+    }
+
+    pub fn builder(self) -> SentenceTokenizerBuilder {
+        let builder = SentenceTokenizerBuilder::new();
+        match self {
+            Preset::Wikipedia => builder
+                .eos('。')
+                .patterns(&[['（', '）'], ['「', '」'], ['『', '』'], ['(', ')']])
+                .line_breaks(LineBreaks::Split),
+            Preset::Fiction => builder
+                .eos_chars(&['。', '！', '？'])
+                .patterns(&[['「', '」'], ['『', '』'], ['（', '）']])
+                .line_breaks(LineBreaks::Split)
+                .glue_eos(true),
+            Preset::SocialMedia => builder
+                .eos_chars(&['。', '！', '？', '!', '?'])
+                .patterns(&[['（', '）'], ['(', ')'], ['「', '」'], ['『', '』']])
+                .line_breaks(LineBreaks::Split)
+                .glue_eos(true)
+                .social_media(true),
+            Preset::Legal => builder
+                .eos('。')
+                .patterns(&[['（', '）'], ['「', '」'], ['『', '』']])
+                .line_breaks(LineBreaks::Split)
+                .legal(true),
+            Preset::Academic => builder
+                .eos_chars(&['．', '。', '！', '？'])
+                .patterns(&[
+                    ['（', '）'],
+                    ['(', ')'],
+                    ['「', '」'],
+                    ['『', '』'],
+                    ['［', '］'],
+                ])
+                .line_breaks(LineBreaks::Join)
+                .glue_eos(true),
+            Preset::SimplifiedChinese => builder
+                .eos_chars(&['。', '！', '？', '；'])
+                .patterns(&[
+                    ['“', '”'],
+                    ['‘', '’'],
+                    ['《', '》'],
+                    ['（', '）'],
+                    ['「', '」'],
+                    ['『', '』'],
+                ])
+                .line_breaks(LineBreaks::Split)
+                .glue_eos(true)
+                .normalization(Normalization::Width)
+                .quote_eos(true),
+            Preset::TraditionalChinese => builder
+                .eos_chars(&['。', '！', '？', '；'])
+                .patterns(&[
+                    ['「', '」'],
+                    ['『', '』'],
+                    ['“', '”'],
+                    ['‘', '’'],
+                    ['《', '》'],
+                    ['〈', '〉'],
+                    ['（', '）'],
+                ])
+                .line_breaks(LineBreaks::Split)
+                .glue_eos(true)
+                .normalization(Normalization::Width)
+                .quote_eos(true),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownPreset;
+
+impl fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown preset, expected one of: ")?;
+        for (i, preset) in Preset::ALL.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(preset.name())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownPreset {}
+
+impl FromStr for Preset {
+    type Err = UnknownPreset;
+
+    fn from_str(name: &str) -> Result<Preset, UnknownPreset> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or(UnknownPreset)
+    }
+}
+
+impl SentenceTokenizer {
+    #[inline]
+    pub fn preset(preset: Preset) -> SentenceTokenizer {
+        preset.builder().build()
+    }
+}
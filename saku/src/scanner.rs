@@ -0,0 +1,245 @@
+use crate::CharTable;
+use aho_corasick::packed::{self, MatchKind};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::CharIndices;
+
+// Control characters are rare compared to the surrounding text, so instead of decoding every char
+// we search the raw UTF-8 bytes for them.
+// Single-byte control chars (line breaks, and the ASCII brackets and terminators added by
+// `Normalization::Width` or the presets) are found with `memchr3`, three at a time, and multi-byte
+// ones (。「」...) with the SIMD packed searcher of `aho-corasick`, which matches on the lead and
+// trail bytes at once. The single-byte chars are kept out of the packed searcher, where they would
+// make it stop at the lead byte of every kana.
+// When the registered chars do not fit the packed searcher (too many of them, or a target
+// without SIMD support), the tokenizer falls back to `char_indices`.
+#[derive(Clone, Debug)]
+pub(crate) struct Scanner {
+    bytes: Vec<[u8; 3]>,
+    searcher: Option<packed::Searcher>,
+}
+
+impl Scanner {
+    pub(crate) fn new(chars: &[char]) -> Option<Scanner> {
+        let (narrow, wide): (Vec<char>, Vec<char>) = chars.iter().partition(|ch| ch.is_ascii());
+
+        let bytes: Vec<[u8; 3]> = narrow
+            .chunks(3)
+            .map(|chunk| {
+                let b: Vec<u8> = chunk.iter().map(|&ch| ch as u8).collect();
+                [b[0], b[b.len().min(2) - 1], b[b.len() - 1]]
+            })
+            .collect();
+        let searcher = if wide.is_empty() {
+            None
+        } else {
+            let patterns: Vec<String> = wide.iter().map(|ch| ch.to_string()).collect();
+            let searcher = packed::Config::new()
+                .match_kind(MatchKind::LeftmostFirst)
+                .builder()
+                .extend(patterns)
+                .build()?;
+            Some(searcher)
+        };
+        Some(Scanner { bytes, searcher })
+    }
+
+    #[inline]
+    pub(crate) fn for_table(char_table: &CharTable) -> Option<Scanner> {
+        let chars: Vec<char> = char_table.chars().collect();
+        Scanner::new(&chars)
+    }
+
+    #[inline(always)]
+    pub(crate) fn scan<'s, 'd>(&'s self, document: &'d str) -> Hits<'s, 'd> {
+        let mut hits = Hits {
+            document,
+            bytes: &self.bytes,
+            matches: self.searcher.as_ref().map(|s| s.find_iter(document)),
+            next_bytes: Vec::with_capacity(self.bytes.len()),
+            next_byte: None,
+            next_wide: None,
+        };
+        hits.next_bytes = (0..self.bytes.len())
+            .map(|group| hits.find_byte(group, 0))
+            .collect();
+        hits.next_byte = hits.first_byte();
+        hits.next_wide = hits.find_wide();
+        hits
+    }
+}
+
+// The scanner of a tokenizer.
+// Tokenizers built at compile time cannot run the searcher construction, so with `std` it is built
+// on first use. Without `std` they go without one and decode every char.
+#[derive(Clone, Debug)]
+pub(crate) struct LazyScanner {
+    #[cfg(feature = "std")]
+    cell: std::sync::OnceLock<Option<Scanner>>,
+    #[cfg(not(feature = "std"))]
+    cell: Option<Scanner>,
+}
+
+impl LazyScanner {
+    #[inline(always)]
+    pub(crate) const fn new() -> LazyScanner {
+        LazyScanner {
+            #[cfg(feature = "std")]
+            cell: std::sync::OnceLock::new(),
+            #[cfg(not(feature = "std"))]
+            cell: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn build(char_table: &CharTable) -> LazyScanner {
+        let scanner = Scanner::for_table(char_table);
+        LazyScanner {
+            #[cfg(feature = "std")]
+            cell: std::sync::OnceLock::from(scanner),
+            #[cfg(not(feature = "std"))]
+            cell: scanner,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn get(&self, char_table: &CharTable) -> Option<&Scanner> {
+        #[cfg(feature = "std")]
+        return self
+            .cell
+            .get_or_init(|| Scanner::for_table(char_table))
+            .as_ref();
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = char_table;
+            self.cell.as_ref()
+        }
+    }
+}
+
+pub(crate) struct Hits<'s, 'd> {
+    document: &'d str,
+    bytes: &'s [[u8; 3]],
+    matches: Option<packed::FindIter<'s, 'd>>,
+    // the next position of a byte of each group of `bytes`
+    next_bytes: Vec<Option<usize>>,
+    // the first of `next_bytes`, with its group
+    next_byte: Option<(usize, usize)>,
+    next_wide: Option<usize>,
+}
+
+impl<'s, 'd> Hits<'s, 'd> {
+    #[inline(always)]
+    fn find_byte(&self, group: usize, from: usize) -> Option<usize> {
+        let [b0, b1, b2] = self.bytes[group];
+        memchr::memchr3(b0, b1, b2, &self.document.as_bytes()[from..]).map(|i| from + i)
+    }
+
+    #[inline(always)]
+    fn first_byte(&self) -> Option<(usize, usize)> {
+        self.next_bytes
+            .iter()
+            .enumerate()
+            .filter_map(|(group, next)| next.map(|b| (b, group)))
+            .min()
+    }
+
+    // Moves the group of bytes found at `b` to its next position.
+    #[inline(always)]
+    fn advance_byte(&mut self, b: usize, group: usize) {
+        self.next_bytes[group] = self.find_byte(group, b + 1);
+        self.next_byte = self.first_byte();
+    }
+
+    #[inline(always)]
+    fn find_wide(&mut self) -> Option<usize> {
+        self.matches.as_mut()?.next().map(|m| m.start())
+    }
+}
+
+impl<'s, 'd> Iterator for Hits<'s, 'd> {
+    type Item = (usize, char);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = match (self.next_byte, self.next_wide) {
+            (Some((b, group)), Some(w)) if b < w => {
+                self.advance_byte(b, group);
+                b
+            }
+            (_, Some(w)) => {
+                self.next_wide = self.find_wide();
+                w
+            }
+            (Some((b, group)), None) => {
+                self.advance_byte(b, group);
+                b
+            }
+            (None, None) => return None,
+        };
+        // Every match starts on a char boundary because UTF-8 lead bytes never appear as trail bytes.
+        self.document[i..].chars().next().map(|ch| (i, ch))
+    }
+}
+
+// Positions of candidate control chars in a document, found either by scanning bytes or by
+// decoding every char.
+pub(crate) enum ControlChars<'s, 'd> {
+    Scan(Hits<'s, 'd>),
+    Decode(CharIndices<'d>),
+}
+
+impl<'s, 'd> Iterator for ControlChars<'s, 'd> {
+    type Item = (usize, char);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ControlChars::Scan(hits) => hits.next(),
+            ControlChars::Decode(chars) => chars.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scanner::Scanner;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn scan_matches_decoding() {
+        let controls = ['。', '「', '」', '\n', '\r', '🔚'];
+        let document = "あ。「い」\r\nうé🔚。a\n。";
+        // the packed searcher is unavailable on some targets
+        let Some(scanner) = Scanner::new(&controls) else {
+            return;
+        };
+
+        let expected: Vec<(usize, char)> = document
+            .char_indices()
+            .filter(|(_, ch)| controls.contains(ch))
+            .collect();
+        let actual: Vec<(usize, char)> = scanner.scan(document).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn scan_many_ascii_chars() {
+        // the ASCII forms of `Normalization::Width` and the brackets of the presets
+        let controls = ['。', '「', '」', '\n', '\r', '.', '!', '?', '(', ')'];
+        let document = "Hi! (あ。) 「い?」\r\nう.";
+        let Some(scanner) = Scanner::new(&controls) else {
+            return;
+        };
+        assert_eq!(3, scanner.bytes.len());
+
+        let expected: Vec<(usize, char)> = document
+            .char_indices()
+            .filter(|(_, ch)| controls.contains(ch))
+            .collect();
+        let actual: Vec<(usize, char)> = scanner.scan(document).collect();
+        assert_eq!(expected, actual);
+    }
+}
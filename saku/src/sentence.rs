@@ -0,0 +1,107 @@
+use crate::{Provision, Timing};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::ops::Range;
+
+// What a sentence is in the layout of the document.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[non_exhaustive]
+pub enum SentenceKind {
+    #[default]
+    Text,
+    // A heading line, found with `SentenceTokenizerBuilder::headings`.
+    Heading,
+    // An item of a list, found with `SentenceTokenizerBuilder::list_items`.
+    ListItem,
+}
+
+impl SentenceKind {
+    #[inline(always)]
+    pub fn is_text(&self) -> bool {
+        *self == SentenceKind::Text
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sentence<'a> {
+    pub text: Cow<'a, str>,
+    // Byte range of the sentence in the tokenized document.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub span: Range<usize>,
+    // Byte offsets in `text` of the spaces inserted between Latin words of joined lines.
+    // Together with the removed line breaks, they relate `text` to the span.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub inserted: Vec<usize>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "SentenceKind::is_text")
+    )]
+    pub kind: SentenceKind,
+    // Byte range in the document of the marker of a list item, such as `・` or `(1)`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub marker: Option<Range<usize>>,
+    // The article, paragraph and item of the sentence, found with `SentenceTokenizerBuilder::legal`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub provision: Option<Provision>,
+    // The speaker of the sentence, found with `Markup::Subtitles` and `Markup::Transcript`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub speaker: Option<String>,
+    // The cues the sentence covers and their times, found with `Markup::Subtitles` and
+    // `Markup::Transcript`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub timing: Option<Timing>,
+}
+
+impl<'a> Sentence<'a> {
+    #[inline(always)]
+    pub(crate) fn new(
+        text: Cow<'a, str>,
+        span: Range<usize>,
+        offset: usize,
+        inserted: Vec<usize>,
+    ) -> Sentence<'a> {
+        Sentence {
+            text,
+            span: span.start + offset..span.end + offset,
+            inserted,
+            kind: SentenceKind::Text,
+            marker: None,
+            provision: None,
+            speaker: None,
+            timing: None,
+        }
+    }
+
+    // Maps a byte offset in `text` to the byte offset in `document`, the tokenized document.
+    // Inserted spaces map to the line break they replace.
+    // The text of sentences from documents with `Markup` is not a copy of the document, so this
+    // mapping does not apply to them.
+    pub fn source_offset(&self, document: &str, offset: usize) -> usize {
+        let source = &document[self.span.clone()];
+        let (mut i, mut j) = (0, 0);
+        let mut inserted = self.inserted.iter().peekable();
+        while i < offset {
+            if inserted.next_if_eq(&&i).is_some() {
+                i += 1;
+                continue;
+            }
+            match source[j..].chars().next() {
+                Some(ch @ ('\n' | '\r')) => j += ch.len_utf8(),
+                Some(ch) => {
+                    i += ch.len_utf8();
+                    j += ch.len_utf8();
+                }
+                None => break,
+            }
+        }
+        if self.inserted.binary_search(&offset).is_err() {
+            j = source.len() - source[j..].trim_start_matches(['\n', '\r']).len();
+        }
+        self.span.start + j
+    }
+}
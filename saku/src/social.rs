@@ -0,0 +1,208 @@
+use crate::{ControlFlow, SentenceTokenizer};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+// The words in brackets that end posts like a terminator, as in `（笑）`.
+const LAUGHTER: [&str; 7] = ["笑", "爆笑", "苦笑", "汗", "泣", "涙", "怒"];
+
+// The longest text between the brackets of a kaomoji, in chars.
+const MAX_FACE: usize = 16;
+
+// The chars that make the faces of kaomoji.
+const FACE: &str = "ω´`｀・▽∀＾^；;дДﾟ゜°∇ε_＿≧≦◕‿｡╹ㅂ￣≡＠∩";
+
+// The arms around the faces of kaomoji, as in `ヽ(・∀・)ﾉ`.
+const LEFT_ARMS: &str = "ヽ＼\\┐σヾ٩ლ⊂";
+const RIGHT_ARMS: &str = "ﾉノ／/┌ゞ۶⊃";
+
+#[inline(always)]
+fn is_emoji(ch: char) -> bool {
+    matches!(ch, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{2B50}' | '\u{2B55}')
+}
+
+// The joiners, variation selectors and keycaps inside emoji sequences.
+#[inline(always)]
+fn is_emoji_part(ch: char) -> bool {
+    is_emoji(ch) || matches!(ch, '\u{200D}' | '\u{FE0F}' | '\u{20E3}')
+}
+
+#[inline(always)]
+fn is_w(ch: char) -> bool {
+    matches!(ch, 'w' | 'W' | 'ｗ' | 'Ｗ')
+}
+
+// The text of a kaomoji between its brackets: a short run of face chars, symbols and at most
+// single Latin letters or digits, without kana or kanji.
+fn is_face(inner: &str) -> bool {
+    let count = inner.chars().count();
+    let mut chars = inner.chars().peekable();
+    let mut words = false;
+    while let Some(ch) = chars.next() {
+        words |=
+            ch.is_ascii_alphanumeric() && chars.peek().is_some_and(char::is_ascii_alphanumeric);
+    }
+    (1..=MAX_FACE).contains(&count)
+        && !words
+        && !inner.chars().any(|ch| {
+            matches!(ch, '\u{3041}'..='\u{3096}' | '\u{30A1}'..='\u{30FA}' | '\u{4E00}'..='\u{9FFF}')
+        })
+        && !inner.contains(['(', '（'])
+        && inner.chars().any(|ch| FACE.contains(ch))
+}
+
+// The end of the kaomoji starting at `start`, such as `(´・ω・｀)` or `ヽ(・∀・)ﾉ`.
+fn kaomoji(document: &str, start: usize) -> Option<usize> {
+    let rest = &document[start..];
+    let arm = rest
+        .chars()
+        .next()
+        .filter(|&ch| LEFT_ARMS.contains(ch))
+        .map_or(0, char::len_utf8);
+    let open = rest[arm..]
+        .chars()
+        .next()
+        .filter(|&ch| ch == '(' || ch == '（')?;
+    let inner = arm + open.len_utf8();
+    // the closing bracket may differ in width, and is looked for only as far as a face may go on
+    // the same line
+    let close = inner
+        + rest[inner..]
+            .char_indices()
+            .take(MAX_FACE + 1)
+            .take_while(|&(_, ch)| ch != '\n' && ch != '\r')
+            .find(|&(_, ch)| ch == ')' || ch == '）')?
+            .0;
+    if !is_face(&rest[inner..close]) {
+        return None;
+    }
+    let mut end = close + rest[close..].chars().next()?.len_utf8();
+    if let Some(arm) = rest[end..]
+        .chars()
+        .next()
+        .filter(|&ch| RIGHT_ARMS.contains(ch))
+    {
+        end += arm.len_utf8();
+    }
+    Some(start + end)
+}
+
+// The end of a laughing or crying word in brackets starting at `start`, such as `（笑）`.
+fn laughter(document: &str, start: usize) -> Option<usize> {
+    let rest = document[start..].strip_prefix(['(', '（'])?;
+    let word = LAUGHTER.iter().find(|word| rest.starts_with(*word))?;
+    let close = rest[word.len()..]
+        .chars()
+        .next()
+        .filter(|&ch| ch == ')' || ch == '）')?;
+    Some(document.len() - rest.len() + word.len() + close.len_utf8())
+}
+
+// The end of the token starting at `start` that ends posts: a kaomoji, a word such as `（笑）`,
+// emoji and symbols such as `♪`, a run of `ｗ` after Japanese text, or an ideographic space after
+// text. Kaomoji are also added to `ranges`.
+fn token(document: &str, start: usize, ranges: &mut Vec<Range<usize>>) -> Option<usize> {
+    if let Some(end) = kaomoji(document, start) {
+        ranges.push(start..end);
+        return Some(end);
+    }
+    if let Some(end) = laughter(document, start) {
+        return Some(end);
+    }
+    let rest = &document[start..];
+    let previous = document[..start].chars().next_back();
+    let len = match rest.chars().next()? {
+        ch if is_emoji(ch) => rest.len() - rest.trim_start_matches(is_emoji_part).len(),
+        // `www.example.com` and English words are not laughter
+        ch if is_w(ch) && previous.is_some_and(|ch| !ch.is_ascii() && !ch.is_whitespace()) => {
+            let len = rest.len() - rest.trim_start_matches(is_w).len();
+            match rest[len..].chars().next() {
+                Some(next) if next.is_ascii_alphanumeric() || next == '.' => return None,
+                _ => len,
+            }
+        }
+        '\u{3000}' if previous.is_some_and(|ch| !ch.is_whitespace()) => {
+            rest.len() - rest.trim_start_matches('\u{3000}').len()
+        }
+        _ => return None,
+    };
+    Some(start + len)
+}
+
+impl SentenceTokenizer {
+    // The end of the run of tokens that end posts starting at `start`, with any terminators
+    // between them, or `start` if there is none.
+    pub(crate) fn social_run(
+        &self,
+        document: &str,
+        start: usize,
+        kaomoji: &mut Vec<Range<usize>>,
+    ) -> usize {
+        let mut end = start;
+        loop {
+            if let Some(next) = token(document, end, kaomoji) {
+                end = next;
+                continue;
+            }
+            let rest = &document[end..];
+            let eos = rest.len()
+                - rest
+                    .trim_start_matches(|ch| {
+                        matches!(self.char_to_control_flow(&ch), Some(ControlFlow::Eos))
+                    })
+                    .len();
+            if end > start && eos > 0 && token(document, end + eos, &mut Vec::new()).is_some() {
+                end += eos;
+                continue;
+            }
+            return end;
+        }
+    }
+
+    // Finds the emoji, kaomoji, laughter and ideographic spaces that end the sentences of posts,
+    // and returns the ends of their runs with the ranges of the kaomoji, in order.
+    pub(crate) fn social_tokens(&self, document: &str) -> (Vec<usize>, Vec<Range<usize>>) {
+        let mut ends: Vec<usize> = Vec::new();
+        let mut kaomoji: Vec<Range<usize>> = Vec::new();
+        let mut i = 0;
+        while let Some(ch) = document[i..].chars().next() {
+            let end = self.social_run(document, i, &mut kaomoji);
+            if end > i {
+                ends.push(end);
+                i = end;
+            } else {
+                i += ch.len_utf8();
+            }
+        }
+        (ends, kaomoji)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{kaomoji, laughter};
+
+    #[test]
+    fn tokens() {
+        assert_eq!(Some("(´・ω・｀)".len()), kaomoji("(´・ω・｀)", 0));
+        assert_eq!(Some("ヽ(・∀・)ﾉ".len()), kaomoji("ヽ(・∀・)ﾉ", 0));
+        assert_eq!(Some("（＾ω＾）".len()), kaomoji("（＾ω＾）です", 0));
+        assert_eq!(None, kaomoji("(注)", 0));
+        assert_eq!(None, kaomoji("(2024)", 0));
+        assert_eq!(None, kaomoji("（笑）", 0));
+        assert_eq!(Some("（笑）".len()), laughter("（笑）", 0));
+        assert_eq!(Some("(爆笑)".len()), laughter("(爆笑)", 0));
+        assert_eq!(None, kaomoji("(・・・・・・・・・・・・・・・・・)", 0));
+        assert_eq!(None, kaomoji("(\n・ω・)", 0));
+    }
+
+    #[test]
+    fn unclosed_brackets() {
+        // the search for a closing bracket stops after the longest face
+        let document = "(・".repeat(100_000);
+        assert_eq!(None, kaomoji(&document, 0));
+        let tokenizer = crate::SentenceTokenizerBuilder::new()
+            .social_media(true)
+            .build();
+        assert_eq!(1, tokenizer.tokenize(&document).len());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{ControlFlow, SentenceTokenizer};
+use alloc::vec::Vec;
+
+// A line at least this share of the typical width (in tenths) is taken as wrapped.
+const FULL_LINE: usize = 8;
+
+// Display width in columns: full-width chars take two, ASCII and half-width katakana one.
+#[inline(always)]
+pub(crate) fn width(line: &str) -> usize {
+    line.chars()
+        .map(|ch| {
+            if ch.is_ascii() || ('\u{FF61}'..='\u{FF9F}').contains(&ch) {
+                1
+            } else {
+                2
+            }
+        })
+        .sum()
+}
+
+// The byte range of every line of `document`, without its line break.
+pub(crate) fn lines(document: &str) -> Vec<(usize, usize)> {
+    let mut lines: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+    for (i, _) in document.match_indices('\n') {
+        let end = if document[..i].ends_with('\r') {
+            i - 1
+        } else {
+            i
+        };
+        lines.push((start, end));
+        start = i + 1;
+    }
+    lines.push((start, document.len()));
+    lines
+}
+
+impl SentenceTokenizer {
+    // Decides which line breaks of `document` are real for `LineBreaks::Auto`, and returns their
+    // positions in order. The others are soft wraps and are removed as in `LineBreaks::Join`.
+    // A line break is real when the next line is blank or indented, when the line ends with a
+    // terminator or a closing bracket, or when the line is clearly shorter than the typical width
+    // of the document, as headings and the last lines of paragraphs are.
+    pub(crate) fn hard_breaks(&self, document: &str) -> Vec<usize> {
+        let lines = lines(document);
+
+        let mut widths: Vec<usize> = lines
+            .iter()
+            .map(|&(start, end)| width(document[start..end].trim_end()))
+            .filter(|&width| width > 0)
+            .collect();
+        widths.sort_unstable();
+        let typical = widths.get(widths.len() * 4 / 5).copied().unwrap_or(0);
+
+        let mut hard: Vec<usize> = Vec::new();
+        for pair in lines.windows(2) {
+            let [(start, end), (next_start, next_end)] = [pair[0], pair[1]];
+            let line = document[start..end].trim_end();
+            let next = &document[next_start..next_end];
+
+            let blank = line.is_empty() || next.trim().is_empty();
+            let indented = next.starts_with(char::is_whitespace);
+            let ended = line.chars().next_back().is_some_and(|ch| {
+                matches!(
+                    self.char_table.get(ch),
+                    Some(ControlFlow::Eos | ControlFlow::RightParens(_))
+                )
+            });
+            let short = width(line) * 10 < typical * FULL_LINE;
+            if blank || indented || ended || short {
+                // the line break, with the `\r` of a `\r\n`
+                hard.extend(end..next_start);
+            }
+        }
+        hard
+    }
+}
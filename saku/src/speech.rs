@@ -0,0 +1,139 @@
+use crate::SentenceTokenizer;
+use alloc::vec::Vec;
+use core::iter::Peekable;
+
+// The sentence-final forms of spoken Japanese that `SentenceTokenizerBuilder::speech_endings`
+// is usually given.
+pub const SPEECH_ENDINGS: [&str; 11] = [
+    "です",
+    "ます",
+    "でした",
+    "ました",
+    "ません",
+    "ませんでした",
+    "でしょう",
+    "ましょう",
+    "ください",
+    "でございます",
+    "よね",
+];
+
+// The sentence-final particles that may follow an ending, as in `ですよね` or `ますかね`.
+const PARTICLES: [&str; 6] = ["よね", "ね", "よ", "か", "な", "わ"];
+
+// The words that start a sentence after an ending with no space, as in `ですそれで`.
+const OPENERS: [&str; 16] = [
+    "それで",
+    "それから",
+    "そして",
+    "でも",
+    "しかし",
+    "だから",
+    "なので",
+    "じゃあ",
+    "ところで",
+    "えーと",
+    "えっと",
+    "えー",
+    "あの",
+    "まあ",
+    "はい",
+    "ええ",
+];
+
+impl SentenceTokenizer {
+    // Finds the ends of the sentences of unpunctuated speech: the `speech_endings` of the
+    // tokenizer and any particles after them, followed by whitespace or a word that opens a
+    // sentence. Returns the byte offsets after the endings, in order.
+    pub(crate) fn speech_ends(&self, document: &str) -> Vec<usize> {
+        let mut ends: Vec<usize> = Vec::new();
+        for ending in self
+            .speech_endings
+            .iter()
+            .filter(|ending| !ending.is_empty())
+        {
+            for (i, _) in document.match_indices(ending.as_str()) {
+                let mut end = i + ending.len();
+                for _ in 0..2 {
+                    match PARTICLES
+                        .iter()
+                        .find(|particle| document[end..].starts_with(*particle))
+                    {
+                        Some(particle) => end += particle.len(),
+                        None => break,
+                    }
+                }
+                let rest = &document[end..];
+                let closes = rest.starts_with(char::is_whitespace)
+                    || OPENERS.iter().any(|opener| rest.starts_with(opener));
+                if closes {
+                    ends.push(end);
+                }
+            }
+        }
+        ends.sort_unstable();
+        ends.dedup();
+        ends
+    }
+}
+
+// The control chars of a document merged with the last chars of the speech endings at `ends`,
+// which are flagged so that they end sentences as terminators.
+pub(crate) struct WithEndings<'e, 'd, I: Iterator<Item = (usize, char)>> {
+    chars: Peekable<I>,
+    ends: &'e [usize],
+    document: &'d str,
+}
+
+impl<'e, 'd, I: Iterator<Item = (usize, char)>> WithEndings<'e, 'd, I> {
+    #[inline(always)]
+    pub(crate) fn new(chars: I, ends: &'e [usize], document: &'d str) -> Self {
+        WithEndings {
+            chars: chars.peekable(),
+            ends,
+            document,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (usize, char)>> Iterator for WithEndings<'_, '_, I> {
+    type Item = (usize, char, bool);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.chars.peek(), self.ends.first()) {
+            (Some(&(i, _)), Some(&end)) if end <= i => {}
+            (None, Some(_)) => {}
+            _ => return self.chars.next().map(|(i, ch)| (i, ch, false)),
+        }
+        let end = self.ends[0];
+        self.ends = &self.ends[1..];
+        let ch = self.document[..end].chars().next_back()?;
+        Some((end - ch.len_utf8(), ch, true))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::SentenceTokenizerBuilder;
+
+    #[test]
+    fn ends() {
+        let tokenizer = SentenceTokenizerBuilder::new()
+            .speech_endings(&super::SPEECH_ENDINGS)
+            .build();
+        let document = "今日は晴れですね 明日は雨ですが出かけますそれで";
+        let ends: alloc::vec::Vec<&str> = tokenizer
+            .speech_ends(document)
+            .into_iter()
+            .map(|end| &document[..end])
+            .collect();
+        assert_eq!(
+            alloc::vec![
+                "今日は晴れですね",
+                "今日は晴れですね 明日は雨ですが出かけます"
+            ],
+            ends
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::html::entity;
+use crate::markup::Stripped;
+use crate::soft_wrap::lines;
+use core::ops::Range;
+
+// The cues of subtitles or the timestamped turns of a transcript that a sentence covers, found
+// with `Markup::Subtitles` and `Markup::Transcript`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Timing {
+    // The indices of the cues, from 0 in the order of the document.
+    pub cues: Range<usize>,
+    // The start of the first cue, in milliseconds.
+    pub start: u64,
+    // The end of the last cue, in milliseconds.
+    pub end: u64,
+}
+
+// The time of a timestamp such as `00:01:02,500`, `01:02.500` or `01:02`, in milliseconds.
+fn timestamp(text: &str) -> Option<u64> {
+    let (clock, fraction) = text.split_once([',', '.']).unwrap_or((text, ""));
+    let millis = match fraction.len() {
+        0 => 0,
+        len @ 1..=3 if fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            fraction.parse::<u64>().ok()? * 10u64.pow(3 - len as u32)
+        }
+        _ => return None,
+    };
+    let (seconds, parts) = clock
+        .split(':')
+        .try_fold((0u64, 0), |(seconds, parts), part| {
+            if part.is_empty()
+                || part.len() > 2 && parts > 0
+                || !part.bytes().all(|b| b.is_ascii_digit())
+            {
+                return None;
+            }
+            let value: u64 = part.parse().ok()?;
+            if parts > 0 && value >= 60 {
+                return None;
+            }
+            Some((seconds * 60 + value, parts + 1))
+        })?;
+    Some(seconds * 1000 + millis).filter(|_| (2..=3).contains(&parts))
+}
+
+// The start and the end of a cue timing line such as `00:00:01,000 --> 00:00:03,500`, which may
+// be followed by WebVTT cue settings.
+fn timing_line(line: &str) -> Option<(u64, u64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((timestamp(start.trim())?, timestamp(end)?))
+}
+
+// Copies the text of a cue line, `document[start..end]`, without its tags such as `<i>` or
+// `<00:00:01.000>`, and with its entities decoded. The voice tag `<v 話者A>` sets the speaker.
+fn cue_text(stripped: &mut Stripped, document: &str, start: usize, end: usize) {
+    let bytes = document.as_bytes();
+    let mut run = start;
+    let mut i = start;
+    while i < end {
+        match bytes[i] {
+            b'<' => {
+                if let Some(close) = document[i..end].find('>') {
+                    stripped.push(document, run..i);
+                    let tag = &document[i + 1..i + close];
+                    if let Some(voice) = tag.strip_prefix('v').filter(|v| v.starts_with([' ', '.']))
+                    {
+                        let name = voice.split_once(' ').map_or("", |(_, name)| name.trim());
+                        stripped
+                            .speaker(Some(name).filter(|name| !name.is_empty()), i..i + close + 1);
+                    }
+                    run = i + close + 1;
+                    i = run;
+                    continue;
+                }
+            }
+            b'&' => {
+                if let Some((ch, entity_end)) = entity(document, i).filter(|&(_, e)| e <= end) {
+                    stripped.push(document, run..i);
+                    stripped.push_replaced(ch.encode_utf8(&mut [0; 4]), i..entity_end);
+                    run = entity_end;
+                    i = run;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    stripped.push(document, run..end);
+}
+
+// Removes the cue numbers, timings, headers and notes of SRT or WebVTT subtitles. The text of the
+// cues is joined as lines, so that an utterance split across cues makes one sentence, and a
+// change of the speaker given by voice tags ends sentences.
+pub(crate) fn strip(document: &str) -> Stripped {
+    let lines = lines(document);
+    let mut stripped = Stripped::new(document.len());
+    let mut k = 0;
+    while k < lines.len() {
+        let (line_start, line_end) = lines[k];
+        k += 1;
+        // the numbers or identifiers of cues, headers, notes and styles have no timing
+        let Some((start, end)) = timing_line(&document[line_start..line_end]) else {
+            continue;
+        };
+        let text_lines = lines[k..]
+            .iter()
+            .position(|&(start, end)| document[start..end].trim().is_empty())
+            .map_or(lines.len(), |n| k + n);
+        let voiced = lines[k..text_lines].iter().any(|&(start, end)| {
+            let line = &document[start..end];
+            line.contains("<v ") || line.contains("<v.")
+        });
+        if !voiced {
+            stripped.speaker(None, line_start..line_end);
+        }
+        let cue = stripped.text.len();
+        for &(start, end) in &lines[k..text_lines] {
+            let next_start = lines.get(k + 1).map_or(document.len(), |&(start, _)| start);
+            cue_text(&mut stripped, document, start, end);
+            stripped.push(document, end..next_start);
+            k += 1;
+        }
+        stripped.cue(cue, start, end);
+    }
+    stripped
+}
+
+// The speaker of a transcript line such as `話者A：…` or `Speaker 1: …`, and the length of the
+// label with its colon. Times such as `10:30` or `午前10:` and URLs such as `https://` are not
+// labels, while a number standing apart, as in `Speaker 1:`, is.
+fn speaker_label(line: &str) -> Option<(&str, usize)> {
+    let colon = line.find(['：', ':'])?;
+    let label = line[..colon].trim_end();
+    let len = colon + line[colon..].chars().next()?.len_utf8();
+    let after = &line[len..];
+    let number = line[..colon].trim_end_matches(|ch: char| ch.is_ascii_digit());
+    let attached_number = line[colon..].starts_with(':')
+        && number.len() < colon
+        && !number.is_empty()
+        && !number.ends_with(char::is_whitespace);
+    let valid = !label.is_empty()
+        && !after.starts_with(|ch: char| ch.is_ascii_digit())
+        && !after.starts_with("//")
+        && !attached_number
+        && label.chars().count() <= 20
+        && !label.starts_with(char::is_whitespace)
+        && !label.bytes().all(|b| b.is_ascii_digit())
+        && !label.contains([
+            '。', '、', '！', '？', '!', '?', '「', '」', '（', '）', '(', ')',
+        ]);
+    Some((label, len)).filter(|_| valid)
+}
+
+// The time of a timestamp in brackets starting `line`, such as `[00:01:23]` or `(01:23.5)`, and
+// its length.
+fn bracketed_timestamp(line: &str) -> Option<(u64, usize)> {
+    let close = match line.chars().next()? {
+        '[' => ']',
+        '(' => ')',
+        _ => return None,
+    };
+    let end = line.find(close)?;
+    Some((timestamp(&line[1..end])?, end + 1))
+}
+
+// Removes the speaker labels of a transcript, whose turns start with `話者A：` or `Speaker 1:`,
+// possibly after a timestamp such as `[00:01:23]`. Every turn ends sentences, and the lines of a
+// turn are joined as lines. The timestamped turns are the cues.
+pub(crate) fn strip_transcript(document: &str) -> Stripped {
+    let lines = lines(document);
+    let mut stripped = Stripped::new(document.len());
+    // the start of the text of the last timestamped turn and its time
+    let mut turn: Option<(usize, u64)> = None;
+    for (k, &(line_start, line_end)) in lines.iter().enumerate() {
+        let next_start = lines.get(k + 1).map_or(document.len(), |&(start, _)| start);
+        let line = &document[line_start..line_end];
+        let indent = line.len() - line.trim_start().len();
+        if line.trim().is_empty() {
+            stripped.end_block();
+            continue;
+        }
+        let mut start = line_start + indent;
+        let time = bracketed_timestamp(&document[start..line_end]);
+        if let Some((_, len)) = time {
+            start += len;
+            start += document[start..line_end].len() - document[start..line_end].trim_start().len();
+        }
+        let label = speaker_label(&document[start..line_end]);
+        if time.is_some() || label.is_some() {
+            stripped.end_block();
+        }
+        if let Some((time, _)) = time {
+            if let Some((cue, cue_start)) = turn.take() {
+                stripped.cue(cue, cue_start, time);
+            }
+            turn = Some((stripped.text.len(), time));
+        }
+        if let Some((label, len)) = label {
+            stripped.speaker(Some(label), line_start..start + len);
+            start += len;
+            start += document[start..line_end].len() - document[start..line_end].trim_start().len();
+        }
+        stripped.push(document, start..line_end);
+        stripped.push(document, line_end..next_start);
+    }
+    if let Some((cue, time)) = turn {
+        stripped.cue(cue, time, time);
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{speaker_label, timestamp};
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(Some(3_723_500), timestamp("01:02:03,500"));
+        assert_eq!(Some(62_500), timestamp("01:02.5"));
+        assert_eq!(Some(62_000), timestamp("01:02"));
+        assert_eq!(None, timestamp("1:75"));
+        assert_eq!(None, timestamp("12"));
+    }
+
+    #[test]
+    fn speaker_labels() {
+        assert_eq!(
+            Some(("話者A", "話者A：".len())),
+            speaker_label("話者A：こんにちは。")
+        );
+        assert_eq!(
+            Some(("Speaker 1", "Speaker 1:".len())),
+            speaker_label("Speaker 1: Hi.")
+        );
+        assert_eq!(None, speaker_label("10:30に集合。"));
+        assert_eq!(None, speaker_label("はい、つまり："));
+        assert_eq!(None, speaker_label("午前10:30に集合しました。"));
+        assert_eq!(None, speaker_label("https://example.com を見て。"));
+        assert_eq!(None, speaker_label("午前10: 集合しました。"));
+    }
+}
@@ -1,12 +1,36 @@
-use crate::{CharTable, ControlFlow, SentenceTokenizerBuilder};
-use std::borrow::Cow;
+use crate::builder::{DEFAULT_EOS, DEFAULT_PATTERNS};
+use crate::scanner::ControlChars;
+use crate::speech::WithEndings;
+use crate::{aozora, html, markdown, subtitles};
+use crate::{
+    static_table_size, CharTable, ControlFlow, LazyScanner, LineBreaks, Markup, Normalization,
+    SentenceTokenizerBuilder, StaticTable, WhitespacePolicy,
+};
+use crate::{Sentence, SentenceKind};
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+use core::ops::Range;
 
 #[derive(Clone, Debug)]
 pub struct SentenceTokenizer {
     pub eos: char,
-    pub(crate) eos_size: usize,
+    pub(crate) eos_chars: Cow<'static, [char]>,
+    pub(crate) patterns: Cow<'static, [[char; 2]]>,
+    pub(crate) line_breaks: LineBreaks,
+    pub(crate) glue_eos: bool,
+    pub(crate) normalization: Normalization,
+    pub(crate) whitespace: WhitespacePolicy,
+    pub(crate) headings: bool,
+    pub(crate) list_items: bool,
+    pub(crate) legal: bool,
+    pub(crate) markup: Markup,
+    pub(crate) keep_ruby: bool,
+    pub(crate) speech_endings: Vec<String>,
+    pub(crate) social_media: bool,
+    pub(crate) quote_eos: bool,
+    pub(crate) latin: bool,
     pub(crate) num_parens: u8,
     pub(crate) char_table: CharTable,
+    pub(crate) scanner: LazyScanner,
 }
 
 impl Default for SentenceTokenizer {
@@ -17,142 +41,555 @@ impl Default for SentenceTokenizer {
 }
 
 impl SentenceTokenizer {
+    // The default tokenizer, usable in `static` items.
+    #[inline]
+    pub const fn new() -> SentenceTokenizer {
+        const EOS: [char; 1] = [DEFAULT_EOS];
+        const TABLE: StaticTable<{ static_table_size(&EOS, &DEFAULT_PATTERNS) }> =
+            StaticTable::new(&EOS, &DEFAULT_PATTERNS);
+        SentenceTokenizer::from_static(&TABLE)
+    }
+
+    #[inline]
+    pub const fn from_static<const N: usize>(table: &'static StaticTable<N>) -> SentenceTokenizer {
+        // the brackets are numbered with `u8`
+        assert!(
+            table.patterns.len() <= u8::MAX as usize,
+            "at most 255 patterns are supported"
+        );
+        SentenceTokenizer {
+            eos: if table.eos.is_empty() {
+                '\0'
+            } else {
+                table.eos[0]
+            },
+            eos_chars: Cow::Borrowed(table.eos),
+            patterns: Cow::Borrowed(table.patterns),
+            line_breaks: LineBreaks::Join,
+            glue_eos: false,
+            normalization: Normalization::None,
+            whitespace: WhitespacePolicy::new(),
+            headings: false,
+            list_items: false,
+            legal: false,
+            markup: Markup::None,
+            keep_ruby: false,
+            speech_endings: Vec::new(),
+            social_media: false,
+            quote_eos: false,
+            latin: false,
+            num_parens: table.patterns.len() as u8,
+            char_table: table.char_table(),
+            scanner: LazyScanner::new(),
+        }
+    }
+
+    // Returns a builder with the configuration of this tokenizer.
+    #[inline]
+    pub fn to_builder(&self) -> SentenceTokenizerBuilder {
+        SentenceTokenizerBuilder {
+            eos: self.eos_chars.to_vec(),
+            patterns: self.patterns.to_vec(),
+            line_breaks: self.line_breaks,
+            glue_eos: self.glue_eos,
+            normalization: self.normalization,
+            whitespace: self.whitespace,
+            headings: self.headings,
+            list_items: self.list_items,
+            legal: self.legal,
+            markup: self.markup,
+            keep_ruby: self.keep_ruby,
+            speech_endings: self.speech_endings.clone(),
+            social_media: self.social_media,
+            quote_eos: self.quote_eos,
+            latin: self.latin,
+        }
+    }
+
     #[inline(always)]
-    fn char_to_control_flow(&self, ch: &char) -> Option<&ControlFlow> {
+    pub(crate) fn char_to_control_flow(&self, ch: &char) -> Option<&ControlFlow> {
         // self.chmap.get(ch)
         self.char_table.get(*ch)
     }
 
+    #[inline(always)]
+    fn control_chars<'s, 'd>(&'s self, document: &'d str) -> ControlChars<'s, 'd> {
+        match self.scanner.get(&self.char_table) {
+            Some(scanner) => ControlChars::Scan(scanner.scan(document)),
+            None => ControlChars::Decode(document.char_indices()),
+        }
+    }
+
     #[inline(always)]
     pub fn tokenize<'a>(&self, document: &'a str) -> Vec<Cow<'a, str>> {
-        self.tokenize_ignore_line_breaks(document)
+        if self.markup != Markup::None
+            || !self.speech_endings.is_empty()
+            || self.social_media
+            || self.latin
+        {
+            return self
+                .tokenize_with_spans(document)
+                .into_iter()
+                .map(|sentence| sentence.text)
+                .collect();
+        }
+        match self.line_breaks {
+            LineBreaks::Join if !self.headings && !self.list_items && !self.legal => {
+                self.tokenize_ignore_line_breaks(document)
+            }
+            LineBreaks::Split if !self.headings && !self.list_items && !self.legal => self
+                .tokenize_raw(document)
+                .into_iter()
+                .map(Cow::Borrowed)
+                .collect(),
+            _ => self
+                .tokenize_with_spans(document)
+                .into_iter()
+                .map(|sentence| sentence.text)
+                .collect(),
+        }
     }
 
     #[inline(always)]
-    pub fn process_line_breaks<'a>(
+    pub fn process_line_breaks(
         &self,
         sentence: &mut String,
         start: &mut usize,
         i: usize,
-        document: &'a str,
+        document: &str,
     ) {
         sentence.push_str(&document[*start..i]);
         *start = i + 1;
     }
 
+    // Returns the end of a sentence whose terminator ends at `end`.
+    // With `glue_eos`, a run of terminators such as `！？` or `。。。` stays in one sentence.
     #[inline(always)]
-    pub fn process_left_parens<'a>(
-        &self,
-        flags: &mut Vec<u8>,
-        nest_count: &mut u8,
-        flag_id: usize,
-    ) {
+    fn eos_end(&self, document: &str, mut end: usize) -> usize {
+        if self.glue_eos {
+            while let Some(ch) = document[end..].chars().next() {
+                match self.char_to_control_flow(&ch) {
+                    Some(ControlFlow::Eos) => end += ch.len_utf8(),
+                    _ => break,
+                }
+            }
+        }
+        if self.social_media {
+            end = self.social_run(document, end, &mut Vec::new());
+        }
+        end
+    }
+
+    // Whether the closing bracket at `i` ends the sentence with `quote_eos`: it closes the
+    // outermost bracket right after a terminator, as in `“今天很好。”`.
+    #[inline(always)]
+    fn closes_quote(&self, document: &str, i: usize, start: usize, nest_count: u8) -> bool {
+        self.quote_eos
+            && nest_count == 0
+            && i > start
+            && document[..i]
+                .chars()
+                .next_back()
+                .is_some_and(|ch| matches!(self.char_to_control_flow(&ch), Some(ControlFlow::Eos)))
+    }
+
+    #[inline(always)]
+    pub fn process_left_parens(&self, flags: &mut [u8], nest_count: &mut u8, flag_id: usize) {
         flags[flag_id] += 1;
         *nest_count += 1;
     }
 
     #[inline(always)]
-    pub fn process_right_parens<'a>(
-        &self,
-        flags: &mut Vec<u8>,
-        nest_count: &mut u8,
-        flag_id: usize,
-    ) {
+    pub fn process_right_parens(&self, flags: &mut [u8], nest_count: &mut u8, flag_id: usize) {
         if flags[flag_id] > 0 {
             flags[flag_id] -= 1;
             *nest_count -= 1;
         }
     }
 
+    // Returns the document trimmed by the whitespace policy and the offset of the trimmed part.
+    #[inline(always)]
+    fn trim_document<'a>(&self, document: &'a str) -> (&'a str, usize) {
+        if !self.whitespace.trim_document {
+            return (document, 0);
+        }
+        let end = document.len() - self.whitespace.trailing(document);
+        let start = self.whitespace.leading(&document[..end], true);
+        (&document[start..end], start)
+    }
+
+    // Trims a sentence of `document` by the whitespace policy, or returns `None` for a blank one.
+    // Returns the trimmed span and how many bytes to remove from each end of the sentence text,
+    // which lacks the line breaks removed in the join mode.
+    #[inline(always)]
+    fn trim_sentence(
+        &self,
+        document: &str,
+        span: Range<usize>,
+    ) -> Option<(Range<usize>, usize, usize)> {
+        if !self.whitespace.trim_sentences {
+            return Some((span, 0, 0));
+        }
+        let source = &document[span.clone()];
+        if self.whitespace.trailing(source) == source.len() {
+            return None;
+        }
+        let line_start = span.start == 0 || document[..span.start].ends_with(['\n', '\r']);
+        let front = self.whitespace.leading(source, line_start);
+        let back = self.whitespace.trailing(source);
+        let line_breaks = |text: &str| text.bytes().filter(|&b| b == b'\n' || b == b'\r').count();
+        Some((
+            span.start + front..span.end - back,
+            front - line_breaks(&source[..front]),
+            back - line_breaks(&source[source.len() - back..]),
+        ))
+    }
+
     #[inline(always)]
     pub fn tokenize_ignore_line_breaks<'a>(&self, document: &'a str) -> Vec<Cow<'a, str>> {
-        let document: &'a str = document.trim();
-        let mut start: usize = 0;
+        let (document, _) = self.trim_document(document);
         let mut sentences: Vec<Cow<'a, str>> = Vec::new();
+        self.split_joined(document, &[], &[], &[], |sentence, span, _| {
+            if let Some((_, front, back)) = self.trim_sentence(document, span) {
+                sentences.push(trim_text(sentence, front, back))
+            }
+        });
+        sentences
+    }
+
+    // This function returns a vector of references of sentences, considering line breaks as the terminator.
+    // `tokenize` and `tokenize_raw` have a different return type to improve performance.
+    #[inline]
+    pub fn tokenize_raw<'a>(&self, document: &'a str) -> Vec<&'a str> {
+        let (document, _) = self.trim_document(document);
+        let mut sentences: Vec<&'a str> = Vec::new();
+        self.split_lines(document, &[], &[], |sentence, span| {
+            if let Some((_, front, back)) = self.trim_sentence(document, span) {
+                sentences.push(&sentence[front..sentence.len() - back])
+            }
+        });
+        sentences
+    }
+
+    // Same as `tokenize`, but each sentence also carries its byte range in `document`.
+    // In the join mode, the range of a sentence includes the line breaks removed from its text.
+    // With markup, the text of the sentences has no markup, and their spans cover the markup inside.
+    #[inline]
+    pub fn tokenize_with_spans<'a>(&self, document: &'a str) -> Vec<Sentence<'a>> {
+        let stripped = match self.markup {
+            Markup::None => return self.split_with_spans(document, &[], &[]),
+            Markup::Aozora => aozora::strip(document, self.keep_ruby),
+            Markup::Markdown => markdown::strip(document),
+            Markup::Html => html::strip(document),
+            Markup::Subtitles => subtitles::strip(document),
+            Markup::Transcript => subtitles::strip_transcript(document),
+        };
+        self.split_with_spans(&stripped.text, &stripped.breaks, &stripped.opaque)
+            .into_iter()
+            .map(|sentence| stripped.restore(sentence))
+            .collect()
+    }
+
+    // Splits `document` with the line breaks of `breaks` ending sentences, and ignoring the control
+    // chars in `opaque`.
+    #[inline(always)]
+    fn split_with_spans<'a>(
+        &self,
+        document: &'a str,
+        breaks: &[usize],
+        opaque: &[Range<usize>],
+    ) -> Vec<Sentence<'a>> {
+        let (trimmed, offset) = self.trim_document(document);
+        let within = |i: &usize| (offset..offset + trimmed.len()).contains(i);
+        let mut skip: Vec<Range<usize>> = opaque
+            .iter()
+            .filter(|range| within(&range.start))
+            .map(|range| range.start - offset..(range.end - offset).min(trimmed.len()))
+            .collect();
+        let breaks: Vec<usize> = breaks
+            .iter()
+            .filter(|i| within(i))
+            .map(|i| i - offset)
+            .collect();
+        let mut sentences: Vec<Sentence<'a>> = Vec::new();
+        let headings = if self.headings {
+            self.heading_lines(trimmed)
+        } else {
+            Vec::new()
+        };
+        let markers = if self.list_items {
+            self.list_markers(trimmed)
+        } else {
+            Vec::new()
+        };
+        skip.extend(markers.iter().cloned());
+        let (social_ends, kaomoji) = if self.social_media {
+            self.social_tokens(trimmed)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        skip.extend(kaomoji);
+        let (latin_ends, terminators) = if self.latin {
+            self.latin_ends(trimmed)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        skip.extend(terminators);
+        skip.sort_unstable_by_key(|range| range.start);
+        let (legal_breaks, provisions) = if self.legal {
+            self.provisions(trimmed)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let mut ends = self.speech_ends(trimmed);
+        ends.retain(|end| !skip.iter().any(|range| range.contains(&(end - 1))));
+        ends.extend(social_ends);
+        ends.extend(latin_ends);
+        ends.sort_unstable();
+        ends.dedup();
+        let mut push = |text, span: Range<usize>, inserted: &[usize]| {
+            if let Some((span, front, back)) = self.trim_sentence(trimmed, span) {
+                let text = trim_text(text, front, back);
+                let inserted = inserted.iter().map(|&i| i - front).collect();
+                let marker = markers
+                    .iter()
+                    .find(|marker| span.start <= marker.start && marker.end <= span.end)
+                    .cloned();
+                let kind = if marker.is_some() {
+                    SentenceKind::ListItem
+                } else if headings
+                    .iter()
+                    .any(|line| line.start <= span.start && span.end <= line.end)
+                {
+                    SentenceKind::Heading
+                } else {
+                    SentenceKind::Text
+                };
+                let provision = match provisions.partition_point(|&(start, _)| start <= span.start)
+                {
+                    0 => None,
+                    k => Some(provisions[k - 1].1.clone()),
+                };
+                sentences.push(Sentence {
+                    kind,
+                    marker: marker.map(|marker| marker.start + offset..marker.end + offset),
+                    provision,
+                    ..Sentence::new(text, span, offset, inserted)
+                })
+            }
+        };
+        match self.line_breaks {
+            LineBreaks::Split if !self.legal => {
+                self.split_lines(trimmed, &skip, &ends, |text, span| {
+                    push(Cow::Borrowed(text), span, &[])
+                })
+            }
+            _ => {
+                let mut hard = match self.line_breaks {
+                    LineBreaks::Auto => self.hard_breaks(trimmed),
+                    _ => Vec::new(),
+                };
+                hard.extend(self.heading_breaks(trimmed, &headings));
+                hard.extend(self.list_breaks(trimmed, &markers));
+                hard.extend(legal_breaks);
+                hard.extend(breaks);
+                hard.sort_unstable();
+                hard.dedup();
+                self.split_joined(trimmed, &hard, &skip, &ends, &mut push)
+            }
+        }
+        sentences
+    }
+
+    // Splits `document`, joining lines except at the positions in `hard`, which end sentences.
+    // The control chars in the ranges of `skip`, such as list markers, are ignored, and the speech
+    // endings at `ends` end sentences as terminators.
+    #[inline(always)]
+    fn split_joined<'a, F>(
+        &self,
+        document: &'a str,
+        mut hard: &[usize],
+        mut skip: &[Range<usize>],
+        ends: &[usize],
+        mut emit: F,
+    ) where
+        F: FnMut(Cow<'a, str>, Range<usize>, &[usize]),
+    {
+        let mut start: usize = 0;
+        // where the text of the current sentence begins, before any removed line break
+        let mut begin: usize = 0;
         let mut sentence: String = String::new();
+        // offsets in `sentence` of the spaces inserted between words of joined lines
+        let mut inserted: Vec<usize> = Vec::new();
         let mut flags: Vec<u8> = vec![0; self.num_parens as usize];
         let mut nest_count: u8 = 0;
 
-        for (i, ch) in document.char_indices() {
-            if let Some(flow) = self.char_to_control_flow(&ch) {
+        for (i, ch, ending) in WithEndings::new(self.control_chars(document), ends, document) {
+            let flow = match ending {
+                true => Some(&ControlFlow::Eos),
+                false => self.char_to_control_flow(&ch),
+            };
+            if let Some(flow) = flow {
+                while skip.first().is_some_and(|range| range.end <= i) {
+                    skip = &skip[1..];
+                }
+                if !ending && skip.first().is_some_and(|range| range.start <= i) {
+                    continue;
+                }
                 match *flow {
                     ControlFlow::LineBreaks => {
-                        self.process_line_breaks(&mut sentence, &mut start, i, &document)
+                        while hard.first().is_some_and(|&h| h < i) {
+                            hard = &hard[1..];
+                        }
+                        if hard.first() == Some(&i) {
+                            if !sentence.is_empty() {
+                                sentence.push_str(&document[start..i]);
+                                emit(Cow::Owned(sentence), begin..i, &inserted);
+                                sentence = String::new();
+                                inserted.clear();
+                            } else if i != start {
+                                emit(Cow::Borrowed(&document[start..i]), start..i, &[]);
+                            }
+                            flags.iter_mut().for_each(|flag| *flag = 0);
+                            nest_count = 0;
+                            start = i + 1;
+                            begin = i + 1;
+                            continue;
+                        }
+                        if sentence.is_empty() && i == start {
+                            begin = i + 1;
+                        }
+                        self.process_line_breaks(&mut sentence, &mut start, i, document);
+                        // `This is a\nsentence.` is joined as `This is a sentence.`
+                        let before = sentence.chars().next_back();
+                        let after = document[i + 1..].chars().next();
+                        if before.is_some_and(is_word_char) && after.is_some_and(is_word_char) {
+                            inserted.push(sentence.len());
+                            sentence.push(' ');
+                        }
+                        continue;
                     }
                     ControlFlow::LeftParens(flag_id) => {
-                        self.process_left_parens(&mut flags, &mut nest_count, flag_id as usize)
+                        self.process_left_parens(&mut flags, &mut nest_count, flag_id as usize);
+                        continue;
                     }
                     ControlFlow::RightParens(flag_id) => {
-                        self.process_right_parens(&mut flags, &mut nest_count, flag_id as usize)
+                        self.process_right_parens(&mut flags, &mut nest_count, flag_id as usize);
+                        if !self.closes_quote(document, i, start, nest_count) {
+                            continue;
+                        }
                     }
                     ControlFlow::Eos => {
-                        if nest_count > 0 {
+                        if nest_count > 0 || i < start {
                             continue;
                         }
-                        if sentence.is_empty() {
-                            sentences.push(Cow::Borrowed(&document[start..i + self.eos_size]));
-                        } else {
-                            sentence.push_str(&document[start..i + self.eos_size]);
-                            sentences.push(Cow::Owned(sentence));
-                            sentence = String::new();
-                        }
-                        start = i + self.eos_size;
                     }
                 }
+                let end = self.eos_end(document, i + ch.len_utf8());
+                if sentence.is_empty() {
+                    emit(Cow::Borrowed(&document[start..end]), start..end, &[]);
+                } else {
+                    sentence.push_str(&document[start..end]);
+                    emit(Cow::Owned(sentence), begin..end, &inserted);
+                    sentence = String::new();
+                    inserted.clear();
+                }
+                start = end;
+                begin = end;
             }
         }
         if start < document.len() {
             if sentence.is_empty() {
-                sentences.push(Cow::Borrowed(&document[start..]));
+                emit(
+                    Cow::Borrowed(&document[start..]),
+                    start..document.len(),
+                    &[],
+                );
             } else {
                 sentence.push_str(&document[start..]);
-                sentences.push(Cow::Owned(sentence));
+                emit(Cow::Owned(sentence), begin..document.len(), &inserted);
             }
         }
-
-        sentences
     }
 
-    // This function returns a vector of references of sentences, considering line breaks as the terminator.
-    // `tokenize` and `tokenize_raw` have a different return type to improve performance.
-    #[inline]
-    pub fn tokenize_raw<'a>(&self, document: &'a str) -> Vec<&'a str> {
-        let document = document.trim();
+    #[inline(always)]
+    fn split_lines<'a, F>(
+        &self,
+        document: &'a str,
+        mut skip: &[Range<usize>],
+        ends: &[usize],
+        mut emit: F,
+    ) where
+        F: FnMut(&'a str, Range<usize>),
+    {
         let mut start: usize = 0;
-        let mut sentences: Vec<&'a str> = Vec::new();
         let mut flags: Vec<u8> = vec![0; self.num_parens as usize];
         let mut nest_count: u8 = 0;
 
-        for (i, ch) in document.char_indices() {
-            if let Some(flow) = self.char_to_control_flow(&ch) {
+        for (i, ch, ending) in WithEndings::new(self.control_chars(document), ends, document) {
+            let flow = match ending {
+                true => Some(&ControlFlow::Eos),
+                false => self.char_to_control_flow(&ch),
+            };
+            if let Some(flow) = flow {
+                while skip.first().is_some_and(|range| range.end <= i) {
+                    skip = &skip[1..];
+                }
+                if !ending && skip.first().is_some_and(|range| range.start <= i) {
+                    continue;
+                }
                 match *flow {
                     ControlFlow::LineBreaks => {
                         if i != start {
-                            sentences.push(&document[start..i]);
+                            emit(&document[start..i], start..i);
                         }
+                        flags.iter_mut().for_each(|flag| *flag = 0);
                         nest_count = 0;
                         start = i + 1;
+                        continue;
                     }
                     ControlFlow::LeftParens(flag_id) => {
-                        self.process_left_parens(&mut flags, &mut nest_count, flag_id as usize)
+                        self.process_left_parens(&mut flags, &mut nest_count, flag_id as usize);
+                        continue;
                     }
                     ControlFlow::RightParens(flag_id) => {
-                        self.process_right_parens(&mut flags, &mut nest_count, flag_id as usize)
+                        self.process_right_parens(&mut flags, &mut nest_count, flag_id as usize);
+                        if !self.closes_quote(document, i, start, nest_count) {
+                            continue;
+                        }
                     }
                     ControlFlow::Eos => {
-                        if nest_count > 0 {
+                        if nest_count > 0 || i < start {
                             continue;
                         }
-                        sentences.push(&document[start..i + self.eos_size]);
-                        start = i + self.eos_size;
                     }
                 }
+                let end = self.eos_end(document, i + ch.len_utf8());
+                emit(&document[start..end], start..end);
+                start = end;
             }
         }
         if start < document.len() {
-            sentences.push(&document[start..]);
+            emit(&document[start..], start..document.len());
         }
+    }
+}
 
-        sentences
+// Letters and digits of Latin script, which are separated by a space where lines are joined.
+// Full-width forms are left out as they are written without spaces in Japanese text.
+#[inline(always)]
+fn is_word_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || (ch.is_alphabetic() && ('\u{C0}'..='\u{24F}').contains(&ch))
+}
+
+#[inline(always)]
+fn trim_text(text: Cow<'_, str>, front: usize, back: usize) -> Cow<'_, str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(&text[front..text.len() - back]),
+        Cow::Owned(mut text) => {
+            text.truncate(text.len() - back);
+            text.drain(..front);
+            Cow::Owned(text)
+        }
     }
 }
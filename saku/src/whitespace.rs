@@ -0,0 +1,117 @@
+// How the tokenizer trims whitespace around the document and its sentences.
+// Trimming only moves the ends of sentences and their spans; whitespace inside a sentence is kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct WhitespacePolicy {
+    pub(crate) trim_document: bool,
+    pub(crate) trim_sentences: bool,
+    pub(crate) keep_indent: bool,
+    pub(crate) ideographic_space: bool,
+}
+
+impl Default for WhitespacePolicy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WhitespacePolicy {
+    // Trims the document only, as `str::trim` does.
+    #[inline(always)]
+    pub const fn new() -> WhitespacePolicy {
+        WhitespacePolicy {
+            trim_document: true,
+            trim_sentences: false,
+            keep_indent: false,
+            ideographic_space: true,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get_trim_document(&self) -> bool {
+        self.trim_document
+    }
+
+    #[inline(always)]
+    pub fn get_trim_sentences(&self) -> bool {
+        self.trim_sentences
+    }
+
+    #[inline(always)]
+    pub fn get_keep_indent(&self) -> bool {
+        self.keep_indent
+    }
+
+    #[inline(always)]
+    pub fn get_ideographic_space(&self) -> bool {
+        self.ideographic_space
+    }
+
+    // Removes the whitespace around the document before splitting it.
+    #[inline(always)]
+    pub fn trim_document(self, trim_document: bool) -> WhitespacePolicy {
+        WhitespacePolicy {
+            trim_document,
+            ..self
+        }
+    }
+
+    // Removes the whitespace around each sentence, such as the space in `。 次の文`.
+    // Sentences made only of whitespace are dropped.
+    #[inline(always)]
+    pub fn trim_sentences(self, trim_sentences: bool) -> WhitespacePolicy {
+        WhitespacePolicy {
+            trim_sentences,
+            ..self
+        }
+    }
+
+    // Keeps the indentation of a sentence or document that starts a line, such as a paragraph
+    // indented with a 全角スペース, while trimming the rest of the leading whitespace.
+    #[inline(always)]
+    pub fn keep_indent(self, keep_indent: bool) -> WhitespacePolicy {
+        WhitespacePolicy {
+            keep_indent,
+            ..self
+        }
+    }
+
+    // Treats U+3000 IDEOGRAPHIC SPACE as whitespace.
+    #[inline(always)]
+    pub fn ideographic_space(self, ideographic_space: bool) -> WhitespacePolicy {
+        WhitespacePolicy {
+            ideographic_space,
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_space(self, ch: char) -> bool {
+        ch.is_whitespace() && (self.ideographic_space || ch != '\u{3000}')
+    }
+
+    // The length of the leading whitespace to trim from `text`, which starts a line if `line_start`.
+    #[inline(always)]
+    pub(crate) fn leading(self, text: &str, line_start: bool) -> usize {
+        let len = text.len() - text.trim_start_matches(|ch| self.is_space(ch)).len();
+        if !self.keep_indent {
+            return len;
+        }
+        match text[..len].rfind(['\n', '\r']) {
+            Some(i) => i + 1,
+            None if line_start => 0,
+            None => len,
+        }
+    }
+
+    // The length of the trailing whitespace to trim from `text`.
+    #[inline(always)]
+    pub(crate) fn trailing(self, text: &str) -> usize {
+        text.len() - text.trim_end_matches(|ch| self.is_space(ch)).len()
+    }
+}
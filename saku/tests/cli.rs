@@ -0,0 +1,132 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn saku(args: &[&str], input: impl AsRef<[u8]>) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_saku"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_ref())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_text() {
+    let output = saku(&[], "吾輩は猫である。名前は\nまだない。");
+    assert_eq!("吾輩は猫である。\n名前はまだない。\n", output);
+}
+
+#[test]
+fn test_cli_options() {
+    let output = saku(
+        &[
+            "--eos",
+            "。！",
+            "--pattern",
+            "「」",
+            "--line-breaks",
+            "split",
+        ],
+        "「はい！」と答えた！本当に\nすごい。",
+    );
+    assert_eq!("「はい！」と答えた！\n本当に\nすごい。\n", output);
+}
+
+#[test]
+fn test_cli_jsonl() {
+    let output = saku(&["--format", "jsonl"], "どうも。\nこんにちは。");
+    let expected = r#"{"text":"どうも。","start":0,"end":12}
+{"text":"こんにちは。","start":13,"end":31}
+"#;
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn test_cli_preset() {
+    let output = saku(&["--preset", "fiction"], "えっ！？本当？\nうん");
+    assert_eq!("えっ！？\n本当？\nうん\n", output);
+}
+
+#[test]
+fn test_cli_corpus() {
+    use flate2::{write::GzEncoder, Compression};
+    use std::fs;
+
+    let root = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-corpus");
+    let _ = fs::remove_dir_all(&root);
+    let corpus = root.join("corpus");
+    fs::create_dir_all(corpus.join("nested")).unwrap();
+    fs::write(corpus.join("a.txt"), "一つ目。二つ目。").unwrap();
+    let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+    gz.write_all("三つ目。".as_bytes()).unwrap();
+    fs::write(corpus.join("nested/b.txt.gz"), gz.finish().unwrap()).unwrap();
+
+    let output = saku(&["--jobs", "2", corpus.to_str().unwrap()], "");
+    assert_eq!("一つ目。\n二つ目。\n三つ目。\n", output);
+
+    let out = root.join("out");
+    saku(&[corpus.to_str().unwrap(), "-O", out.to_str().unwrap()], "");
+    assert_eq!(
+        "一つ目。\n二つ目。\n",
+        fs::read_to_string(out.join("a.txt")).unwrap()
+    );
+    assert_eq!(
+        "三つ目。\n",
+        fs::read_to_string(out.join("nested/b.txt")).unwrap()
+    );
+}
+
+#[test]
+fn test_cli_shift_jis() {
+    let (input, _, _) = encoding_rs::SHIFT_JIS.encode("abc。漢字です。");
+    let output = saku(&["--format", "jsonl"], &input);
+    let expected = r#"{"text":"abc。","start":0,"end":5,"chars":{"start":0,"end":4}}
+{"text":"漢字です。","start":5,"end":15,"chars":{"start":4,"end":9}}
+"#;
+    assert_eq!(expected, output);
+
+    let output = saku(&["--encoding", "sjis"], &input);
+    assert_eq!("abc。\n漢字です。\n", output);
+}
+
+#[test]
+fn test_cli_jsonl_input() {
+    let input = r#"{"id":1,"body":"どうも。こんにちは。"}"#;
+    let output = saku(&["-i", "jsonl", "--text-field", "body"], input);
+    assert_eq!("どうも。\nこんにちは。\n", output);
+
+    let output = saku(
+        &["-i", "jsonl", "--text-field", "body", "-f", "documents"],
+        input,
+    );
+    let expected = r#"{"id":1,"body":"どうも。こんにちは。","sentences":[{"text":"どうも。","start":0,"end":12},{"text":"こんにちは。","start":12,"end":30}]}
+"#;
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn test_cli_jsonl_errors() {
+    // the text output reports records like the library
+    let mut child = Command::new(env!("CARGO_BIN_EXE_saku"))
+        .args(["-i", "jsonl"])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"[1]").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("line 1: not a JSON object"), "{}", stderr);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "serde")]
+
+use saku::{ConfigFormat, LineBreaks, Normalization, SentenceTokenizer, SentenceTokenizerBuilder};
+
+#[test]
+fn test_load_toml_config() {
+    let config = r#"
+eos = ["。", "！"]
+patterns = [["「", "」"]]
+line_breaks = "split"
+"#;
+    let builder = SentenceTokenizerBuilder::from_config_str(config, ConfigFormat::Toml).unwrap();
+    assert_eq!(&['。', '！'], builder.get_eos());
+    assert_eq!(&[['「', '」']], builder.get_patterns());
+    assert_eq!(LineBreaks::Split, builder.get_line_breaks());
+
+    let tokenizer = builder.build();
+    let expected = vec!["「はい！」と答えた！", "本当に", "すごい。"];
+    let actual = tokenizer.tokenize("「はい！」と答えた！本当に\nすごい。");
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_load_json_config_with_defaults() {
+    let config = r#"{"eos": ["．"]}"#;
+    let builder = SentenceTokenizerBuilder::from_config_str(config, ConfigFormat::Json).unwrap();
+    assert_eq!(&['．'], builder.get_eos());
+    assert_eq!(
+        SentenceTokenizerBuilder::new().get_patterns(),
+        builder.get_patterns()
+    );
+    assert_eq!(LineBreaks::Join, builder.get_line_breaks());
+}
+
+#[test]
+fn test_round_trip_through_files() {
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .eos_chars(&['。', '？'])
+        .patterns(&[['（', '）']])
+        .line_breaks(LineBreaks::Split)
+        .build();
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR"));
+
+    for extension in ["toml", "json"] {
+        let name = format!("config-{}.{}", std::process::id(), extension);
+        let path = dir.join(name);
+        tokenizer.to_builder().save_config_file(&path).unwrap();
+        let loaded = SentenceTokenizer::from_config_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(&['。', '？'], loaded.to_builder().get_eos());
+        assert_eq!(&[['（', '）']], loaded.to_builder().get_patterns());
+        assert_eq!(LineBreaks::Split, loaded.to_builder().get_line_breaks());
+    }
+}
+
+#[test]
+fn test_unknown_config_format() {
+    assert!(SentenceTokenizer::from_config_file("saku.yaml").is_err());
+}
+
+#[test]
+fn test_load_whitespace_policy() {
+    let config = r#"
+normalization = "width"
+
+[whitespace]
+trim_sentences = true
+"#;
+    let builder = SentenceTokenizerBuilder::from_config_str(config, ConfigFormat::Toml).unwrap();
+    assert_eq!(Normalization::Width, builder.get_normalization());
+    let whitespace = builder.get_whitespace();
+    assert!(whitespace.get_trim_sentences());
+    assert!(whitespace.get_trim_document());
+    assert!(!whitespace.get_keep_indent());
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "encoding")]
+
+use saku::{Decoded, Encoding, SentenceTokenizer};
+
+const DOCUMENT: &str = "吾輩は猫である。名前はまだ無い。\nどこで生れたかとんと見当がつかぬ。";
+
+fn encode(encoding: &'static encoding_rs::Encoding, text: &str) -> Vec<u8> {
+    encoding.encode(text).0.into_owned()
+}
+
+#[test]
+fn test_detect() {
+    let cases = [
+        (encoding_rs::UTF_8, Encoding::Utf8),
+        (encoding_rs::SHIFT_JIS, Encoding::ShiftJis),
+        (encoding_rs::EUC_JP, Encoding::EucJp),
+        (encoding_rs::ISO_2022_JP, Encoding::Iso2022Jp),
+    ];
+    for (encoding, expected) in cases {
+        assert_eq!(expected, Encoding::detect(&encode(encoding, DOCUMENT)));
+    }
+    assert_eq!(Ok(Encoding::ShiftJis), "sjis".parse());
+    assert!("latin1".parse::<Encoding>().is_err());
+}
+
+#[test]
+fn test_tokenize_bytes() {
+    let tokenizer = SentenceTokenizer::new();
+    let expected = tokenizer.tokenize(DOCUMENT);
+    for encoding in [
+        encoding_rs::SHIFT_JIS,
+        encoding_rs::EUC_JP,
+        encoding_rs::ISO_2022_JP,
+    ] {
+        let bytes = encode(encoding, DOCUMENT);
+        let sentences = tokenizer.tokenize_bytes(&bytes);
+        let texts: Vec<&str> = sentences.iter().map(|s| s.text.as_ref()).collect();
+        assert_eq!(expected, texts);
+
+        let chars: Vec<char> = DOCUMENT.chars().collect();
+        for sentence in &sentences {
+            let text: String = chars[sentence.chars.clone()].iter().collect();
+            assert_eq!(sentence.text, text.replace('\n', ""));
+        }
+    }
+}
+
+#[test]
+fn test_source_spans() {
+    let tokenizer = SentenceTokenizer::new();
+    let bytes = encode(encoding_rs::SHIFT_JIS, "abc。漢字ｶﾅ。");
+    let sentences = tokenizer.tokenize_bytes(&bytes);
+    assert_eq!(0..5, sentences[0].bytes);
+    assert_eq!(0..4, sentences[0].chars);
+    assert_eq!(5..13, sentences[1].bytes);
+    assert_eq!(4..9, sentences[1].chars);
+    for sentence in &sentences {
+        let (text, _) =
+            encoding_rs::SHIFT_JIS.decode_without_bom_handling(&bytes[sentence.bytes.clone()]);
+        assert_eq!(sentence.text, text);
+    }
+}
+
+#[test]
+fn test_ascii_trail_bytes() {
+    // the trail bytes of 表 (0x95 0x5C), ソ (0x83 0x5C) and 能 (0x94 0x5C) are ASCII
+    let tokenizer = SentenceTokenizer::new();
+    let bytes = encode(encoding_rs::SHIFT_JIS, "表示。ソフト。能力。");
+    let sentences = tokenizer.tokenize_bytes(&bytes);
+    let spans: Vec<_> = sentences.iter().map(|s| s.bytes.clone()).collect();
+    assert_eq!(vec![0..6, 6..14, 14..20], spans);
+    for sentence in &sentences {
+        let (text, _) =
+            encoding_rs::SHIFT_JIS.decode_without_bom_handling(&bytes[sentence.bytes.clone()]);
+        assert_eq!(sentence.text, text);
+    }
+}
+
+#[test]
+fn test_malformed_bytes() {
+    // a Shift_JIS lead byte cut short by ASCII, then a lone lead byte at the end
+    let bytes = b"a\x88b\x82\xa0\x82";
+    let decoded = Decoded::new(bytes, Encoding::ShiftJis);
+    assert_eq!("a\u{FFFD}bあ\u{FFFD}", decoded.text);
+    let sources: Vec<usize> = decoded
+        .text
+        .char_indices()
+        .map(|(i, _)| decoded.source_offset(i))
+        .collect();
+    assert_eq!(vec![0, 1, 2, 3, 5], sources);
+    assert_eq!(bytes.len(), decoded.source_offset(decoded.text.len()));
+}
+
+#[test]
+fn test_utf8_bom() {
+    let tokenizer = SentenceTokenizer::new();
+    let sentences = tokenizer.tokenize_bytes("\u{FEFF}はい。いいえ。".as_bytes());
+    assert_eq!("はい。", sentences[0].text);
+    assert_eq!(3..12, sentences[0].bytes);
+    assert_eq!(0..3, sentences[0].chars);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "serde")]
+
+use saku::{read_jsonl, JsonlError, JsonlMode, JsonlOptions, SentenceTokenizer};
+
+fn tokenize_jsonl(input: &str, options: &JsonlOptions) -> Result<String, JsonlError> {
+    let tokenizer = SentenceTokenizer::new();
+    let mut output = Vec::new();
+    tokenizer.tokenize_jsonl(input.as_bytes(), &mut output, options)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn test_jsonl_sentences() {
+    let input = r#"{"id":"a","text":"どうも。こんにちは。","url":"https://example.com"}
+
+{"text":"はい。","meta":{"lang":"ja"}}
+"#;
+    let expected = r#"{"id":"a","text":"どうも。","url":"https://example.com","index":0,"start":0,"end":12}
+{"id":"a","text":"こんにちは。","url":"https://example.com","index":1,"start":12,"end":30}
+{"text":"はい。","meta":{"lang":"ja"},"id":2,"index":0,"start":0,"end":9}
+"#;
+    assert_eq!(
+        expected,
+        tokenize_jsonl(input, &JsonlOptions::new()).unwrap()
+    );
+}
+
+#[test]
+fn test_jsonl_documents() {
+    let input = r#"{"body":"どうも。こんにちは。","doc":7}"#;
+    let options = JsonlOptions::new()
+        .mode(JsonlMode::Documents)
+        .text_field("body")
+        .sentences_field("sents");
+    let expected = r#"{"body":"どうも。こんにちは。","doc":7,"sents":[{"text":"どうも。","start":0,"end":12},{"text":"こんにちは。","start":12,"end":30}]}
+"#;
+    assert_eq!(expected, tokenize_jsonl(input, &options).unwrap());
+}
+
+#[test]
+fn test_jsonl_custom_fields() {
+    let input = r#"{"doc":7,"body":"はい。"}"#;
+    let options = JsonlOptions::new()
+        .text_field("body")
+        .id_field("doc")
+        .index_field("n");
+    let expected = r#"{"doc":7,"body":"はい。","n":0,"start":0,"end":9}
+"#;
+    assert_eq!(expected, tokenize_jsonl(input, &options).unwrap());
+
+    // fields of the records named like the span are kept
+    let input = r#"{"text":"はい。","start":"2024-01-01","end":"2024-12-31"}"#;
+    let options = JsonlOptions::new()
+        .start_field("span_start")
+        .end_field("span_end");
+    let expected = r#"{"text":"はい。","start":"2024-01-01","end":"2024-12-31","id":0,"index":0,"span_start":0,"span_end":9}
+"#;
+    assert_eq!(expected, tokenize_jsonl(input, &options).unwrap());
+}
+
+#[test]
+fn test_jsonl_errors() {
+    let options = JsonlOptions::new();
+    assert!(matches!(
+        tokenize_jsonl("{\"text\":\"はい。\"}\n{\"body\":1}", &options),
+        Err(JsonlError::MissingText(2, _))
+    ));
+    assert!(matches!(
+        tokenize_jsonl("[1]", &options),
+        Err(JsonlError::NotAnObject(1))
+    ));
+    assert!(matches!(
+        tokenize_jsonl("{", &options),
+        Err(JsonlError::Json(1, _))
+    ));
+
+    let records: Vec<_> = read_jsonl("{\"a\":1}\n\n[1]".as_bytes()).collect();
+    assert!(matches!(records[0], Ok((0, _))));
+    assert!(matches!(records[1], Err(JsonlError::NotAnObject(3))));
+}
@@ -0,0 +1,234 @@
+use saku::{Markup, SentenceKind, SentenceTokenizerBuilder, Timing};
+
+#[test]
+fn test_aozora() {
+    let document = "吾輩は猫である\n夏目漱石\n\n-------------------------------------------------------\n【テキスト中に現れる記号について】\n\n《》：ルビ\n（例）吾輩《わがはい》\n-------------------------------------------------------\n\n　吾輩《わがはい》は猫である。｜名前《なまえ》はまだ無い［＃「無い」に傍点］。\n　どこで生れたかとんと見当《けんとう》がつかぬ。\n\n\n\n底本：「夏目漱石全集1」ちくま文庫、筑摩書房\n";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Aozora)
+        .build();
+    assert_eq!(
+        vec![
+            "吾輩は猫である。",
+            "名前はまだ無い。",
+            "　どこで生れたかとんと見当がつかぬ。"
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    // the spans refer to the raw text, with the markup inside the sentences but not before them
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(
+        "吾輩《わがはい》は猫である。",
+        &document[sentences[0].span.clone()]
+    );
+    assert_eq!(
+        "名前《なまえ》はまだ無い［＃「無い」に傍点］。",
+        &document[sentences[1].span.clone()]
+    );
+
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Aozora)
+        .keep_ruby(true)
+        .build();
+    assert_eq!(
+        "名前《なまえ》はまだ無い。",
+        tokenizer.tokenize(document)[1]
+    );
+}
+
+#[test]
+fn test_aozora_annotations_and_brackets() {
+    // the quotes inside the annotation do not open a bracket
+    let document = "彼は［＃「彼は」に傍点］走った。［＃改ページ］\n次の章。";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Aozora)
+        .build();
+    assert_eq!(
+        vec!["彼は走った。", "次の章。"],
+        tokenizer.tokenize(document)
+    );
+
+    // without a header, the whole text is the body
+    assert_eq!(vec!["一行目。"], tokenizer.tokenize("一行目。\n"));
+}
+
+#[test]
+fn test_markdown() {
+    let document = r#"---
+title: 導入
+---
+
+# はじめに
+
+本書では `saku.tokenize()` の使い方を説明する。詳細は[公式サイト](https://example.com/docs/a.b。c)を参照。
+改行を含む
+段落です。
+
+```rust
+let a = "コード。ブロック。";
+```
+
+- 項目A
+- 項目B。続き
+
+> 引用文です。引用の二文目。
+
+| 名前 | 説明 |
+| --- | --- |
+| 甲 | 最初。二番目 |
+
+見出し2
+-------
+URLは https://example.com/a.html です。
+"#;
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Markdown)
+        .build();
+    assert_eq!(
+        vec![
+            "はじめに",
+            "本書では `saku.tokenize()` の使い方を説明する。",
+            "詳細は公式サイトを参照。",
+            "改行を含む段落です。",
+            "項目A",
+            "項目B。",
+            "続き",
+            "引用文です。",
+            "引用の二文目。",
+            "名前",
+            "説明",
+            "甲",
+            "最初。",
+            "二番目",
+            "見出し2",
+            "URLは https://example.com/a.html です。",
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(SentenceKind::Heading, sentences[0].kind);
+    assert_eq!(SentenceKind::Heading, sentences[14].kind);
+    assert_eq!(SentenceKind::Text, sentences[1].kind);
+    assert_eq!(
+        "詳細は[公式サイト](https://example.com/docs/a.b。c)を参照。",
+        &document[sentences[2].span.clone()]
+    );
+    assert_eq!(
+        "改行を含む\n段落です。",
+        &document[sentences[3].span.clone()]
+    );
+}
+
+#[test]
+fn test_html() {
+    let document = r#"<!DOCTYPE html>
+<html>
+<head><title>ページ</title><script>var s = "スクリプト。";</script></head>
+<body>
+<h1>見出し</h1>
+<p>最初の文です。<a href="https://example.com/a.b">リンク</a>を含む
+  二文目です。<br>改行の後&amp;実体&#12290;</p>
+<ul><li>項目A</li><li>項目B</li></ul>
+<!-- コメント。 -->
+</body>
+</html>"#;
+    let tokenizer = SentenceTokenizerBuilder::new().markup(Markup::Html).build();
+    assert_eq!(
+        vec![
+            "見出し",
+            "最初の文です。",
+            "リンクを含む二文目です。",
+            "改行の後&実体。",
+            "項目A",
+            "項目B",
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(SentenceKind::Heading, sentences[0].kind);
+    assert_eq!(
+        "リンク</a>を含む\n  二文目です。",
+        &document[sentences[2].span.clone()]
+    );
+    assert_eq!(
+        "改行の後&amp;実体&#12290;",
+        &document[sentences[3].span.clone()]
+    );
+}
+
+#[test]
+fn test_subtitles() {
+    let srt = "1\n00:00:01,000 --> 00:00:03,000\n今日はいい天気\n\n2\n00:00:03,200 --> 00:00:05,000\nですね。散歩に\n行きましょう。\n\n3\n00:00:06,000 --> 00:00:07,500\n<i>はい。</i>\n";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Subtitles)
+        .build();
+    assert_eq!(
+        vec!["今日はいい天気ですね。", "散歩に行きましょう。", "はい。"],
+        tokenizer.tokenize(srt)
+    );
+    let sentences = tokenizer.tokenize_with_spans(srt);
+    assert_eq!(
+        Some(Timing {
+            cues: 0..2,
+            start: 1000,
+            end: 5000
+        }),
+        sentences[0].timing
+    );
+    assert_eq!(
+        Some(Timing {
+            cues: 1..2,
+            start: 3200,
+            end: 5000
+        }),
+        sentences[1].timing
+    );
+    assert_eq!("はい。", &srt[sentences[2].span.clone()]);
+
+    let vtt = "WEBVTT\n\nNOTE 話者付きの字幕。\n\n00:01.000 --> 00:02.000 align:start\n<v 田中>それでは始めましょう\n\nintro\n00:02.000 --> 00:03.000\n<v 田中>か。</v>\n\n00:03.500 --> 00:04.000\n<v 佐藤>お願いします\n";
+    let sentences = tokenizer.tokenize_with_spans(vtt);
+    let texts: Vec<&str> = sentences.iter().map(|s| s.text.as_ref()).collect();
+    assert_eq!(vec!["それでは始めましょうか。", "お願いします"], texts);
+    assert_eq!(Some("田中"), sentences[0].speaker.as_deref());
+    assert_eq!(Some("佐藤"), sentences[1].speaker.as_deref());
+    assert_eq!(Some(2..3), sentences[1].timing.clone().map(|t| t.cues));
+}
+
+#[test]
+fn test_transcript() {
+    let document = "[00:00:05] 話者A：本日はお集まりいただき\nありがとうございます。\n[00:00:12] 話者B：よろしくお願いします\n話者A：では始めます。";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .markup(Markup::Transcript)
+        .build();
+    let sentences = tokenizer.tokenize_with_spans(document);
+    let texts: Vec<&str> = sentences.iter().map(|s| s.text.as_ref()).collect();
+    assert_eq!(
+        vec![
+            "本日はお集まりいただきありがとうございます。",
+            "よろしくお願いします",
+            "では始めます。"
+        ],
+        texts
+    );
+    let speakers: Vec<Option<&str>> = sentences.iter().map(|s| s.speaker.as_deref()).collect();
+    assert_eq!(vec![Some("話者A"), Some("話者B"), Some("話者A")], speakers);
+    assert_eq!(
+        Some(Timing {
+            cues: 0..1,
+            start: 5000,
+            end: 12000
+        }),
+        sentences[0].timing
+    );
+    assert_eq!(
+        Some(Timing {
+            cues: 1..2,
+            start: 12000,
+            end: 12000
+        }),
+        sentences[2].timing
+    );
+    assert_eq!("よろしくお願いします", &document[sentences[1].span.clone()]);
+}
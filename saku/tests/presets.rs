@@ -0,0 +1,136 @@
+use saku::{Preset, SentenceTokenizer};
+
+#[test]
+fn test_wikipedia_preset() {
+    let document = "東京都（とうきょうと）は、日本の首都である。「東京」とも呼ばれる。\n概要\n人口は約1400万人。";
+    let tokenizer = SentenceTokenizer::preset(Preset::Wikipedia);
+
+    let expected = vec![
+        "東京都（とうきょうと）は、日本の首都である。",
+        "「東京」とも呼ばれる。",
+        "概要",
+        "人口は約1400万人。",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    // the default tokenizer merges the section heading into the next sentence
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!("概要人口は約1400万人。", default[2]);
+}
+
+#[test]
+fn test_fiction_preset() {
+    let document = "「待って……。行かないで！」\n彼女は叫んだ。えっ！？　どうして？\n「……」";
+    let tokenizer = SentenceTokenizer::preset(Preset::Fiction);
+
+    let expected = vec![
+        "「待って……。行かないで！」",
+        "彼女は叫んだ。",
+        "えっ！？",
+        "　どうして？",
+        "「……」",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!("「待って……。行かないで！」彼女は叫んだ。", default[0]);
+}
+
+#[test]
+fn test_social_media_preset() {
+    let document =
+        "今日のライブ最高だった!!ありがとう（＾ω＾）また行きたい？\nマジか？！(´・ω・｀)";
+    let tokenizer = SentenceTokenizer::preset(Preset::SocialMedia);
+
+    let expected = vec![
+        "今日のライブ最高だった!!",
+        "ありがとう（＾ω＾）",
+        "また行きたい？",
+        "マジか？！(´・ω・｀)",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!(1, default.len());
+}
+
+#[test]
+fn test_legal_preset() {
+    let document = "第一条　この法律は、「個人情報」の適正な取扱いを定める。\n２　前項の規定は、次に掲げる者には適用しない。\n一　国の機関\n二　地方公共団体";
+    let tokenizer = SentenceTokenizer::preset(Preset::Legal);
+
+    let expected = vec![
+        "第一条　この法律は、「個人情報」の適正な取扱いを定める。",
+        "２　前項の規定は、次に掲げる者には適用しない。",
+        "一　国の機関",
+        "二　地方公共団体",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!("一　国の機関二　地方公共団体", default[2]);
+}
+
+#[test]
+fn test_academic_preset() {
+    let document = "本研究では，手法Ａを提案する．実験の結果（表１参照），\n精度が向上した．今後の課題は何か？";
+    let tokenizer = SentenceTokenizer::preset(Preset::Academic);
+
+    let expected = vec![
+        "本研究では，手法Ａを提案する．",
+        "実験の結果（表１参照），精度が向上した．",
+        "今後の課題は何か？",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!(1, default.len());
+}
+
+#[test]
+fn test_simplified_chinese_preset() {
+    let document = "他说：“今天天气很好。”然后就出门了。你去过《红楼梦》里写的地方吗？没有！我们明天再说；好吗?\n“真的吗？！”她问道。";
+    let tokenizer = SentenceTokenizer::preset(Preset::SimplifiedChinese);
+
+    let expected = vec![
+        "他说：“今天天气很好。”",
+        "然后就出门了。",
+        "你去过《红楼梦》里写的地方吗？",
+        "没有！",
+        "我们明天再说；",
+        "好吗?",
+        "“真的吗？！”",
+        "她问道。",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!("他说：“今天天气很好。", default[0]);
+}
+
+#[test]
+fn test_traditional_chinese_preset() {
+    let document = "他說：「今天天氣很好。」然後就出門了。你讀過〈背影〉嗎？讀過。\n『好吧。』她說，「那明天見」。";
+    let tokenizer = SentenceTokenizer::preset(Preset::TraditionalChinese);
+
+    let expected = vec![
+        "他說：「今天天氣很好。」",
+        "然後就出門了。",
+        "你讀過〈背影〉嗎？",
+        "讀過。",
+        "『好吧。』",
+        "她說，「那明天見」。",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let default = SentenceTokenizer::default().tokenize(document);
+    assert_eq!("他說：「今天天氣很好。」然後就出門了。", default[0]);
+}
+
+#[test]
+fn test_preset_names() {
+    for preset in Preset::ALL {
+        assert_eq!(Ok(preset), preset.name().parse());
+    }
+    assert!("news".parse::<Preset>().is_err());
+}
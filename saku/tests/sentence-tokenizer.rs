@@ -1,4 +1,7 @@
-use saku::{SentenceTokenizer, SentenceTokenizerBuilder};
+use saku::{
+    LineBreaks, Normalization, Provision, SentenceKind, SentenceTokenizer,
+    SentenceTokenizerBuilder, WhitespacePolicy, SPEECH_ENDINGS,
+};
 
 #[test]
 fn test_tokenize_short() {
@@ -233,3 +236,483 @@ fn test_tokenize_raw_with_sentence_fragment() {
     let actual = tokenizer.tokenize_raw(document);
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn test_tokenize_raw_parenthesis_closed_on_next_line() {
+    let document = "「どうも\nこんにちは」。名前は。";
+    let tokenizer = SentenceTokenizer::default();
+
+    let expected = vec!["「どうも", "こんにちは」。", "名前は。"];
+    let actual = tokenizer.tokenize_raw(document);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_tokenize_with_spans() {
+    let document = "\n吾輩は猫である。名前は\nまだない。\n";
+    let tokenizer = SentenceTokenizer::default();
+
+    let actual = tokenizer.tokenize_with_spans(document);
+    let texts: Vec<&str> = actual.iter().map(|s| s.text.as_ref()).collect();
+    assert_eq!(vec!["吾輩は猫である。", "名前はまだない。"], texts);
+    assert_eq!("吾輩は猫である。", &document[actual[0].span.clone()]);
+    assert_eq!("名前は\nまだない。", &document[actual[1].span.clone()]);
+}
+
+#[test]
+fn test_tokenize_raw_with_spans() {
+    let document = "吾輩は猫である。名前は\r\nまだない。";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(saku::LineBreaks::Split)
+        .build();
+
+    let actual = tokenizer.tokenize_with_spans(document);
+    let spans: Vec<&str> = actual.iter().map(|s| &document[s.span.clone()]).collect();
+    assert_eq!(vec!["吾輩は猫である。", "名前は", "まだない。"], spans);
+}
+
+#[test]
+fn test_tokenize_batch() {
+    let tokenizer = SentenceTokenizer::new();
+    let documents = ["一つ目。二つ目。", "三つ目。"];
+    let expected = vec![vec!["一つ目。", "二つ目。"], vec!["三つ目。"]];
+    assert_eq!(expected, tokenizer.tokenize_batch(&documents));
+}
+
+#[test]
+fn test_normalization() {
+    let document = "はい｡（ええ。)そう︒ ｢本当。｣";
+    let width = SentenceTokenizerBuilder::new()
+        .normalization(Normalization::Width)
+        .build();
+    assert_eq!(
+        vec!["はい｡", "（ええ。)そう︒ ｢本当。｣"],
+        width.tokenize(document)
+    );
+
+    let nfkc = SentenceTokenizerBuilder::new()
+        .normalization(Normalization::Nfkc)
+        .build();
+    let sentences = nfkc.tokenize_with_spans(document);
+    let texts: Vec<&str> = sentences.iter().map(|s| s.text.as_ref()).collect();
+    assert_eq!(vec!["はい｡", "（ええ。)そう︒", " ｢本当。｣"], texts);
+    for sentence in &sentences {
+        assert_eq!(sentence.text, &document[sentence.span.clone()]);
+    }
+
+    let plain = SentenceTokenizer::new();
+    assert_eq!(
+        vec!["はい｡（ええ。)そう︒ ｢本当。｣"],
+        plain.tokenize(document)
+    );
+}
+
+#[test]
+fn test_whitespace_policy() {
+    let document = "\n　吾輩は猫である。 名前は\nまだ無い。 \n　どこで生れたか。\n";
+
+    let default = SentenceTokenizer::new();
+    assert_eq!(
+        vec![
+            "吾輩は猫である。",
+            " 名前はまだ無い。",
+            " 　どこで生れたか。"
+        ],
+        default.tokenize(document)
+    );
+
+    let policy = WhitespacePolicy::new()
+        .trim_sentences(true)
+        .keep_indent(true);
+    let tokenizer = SentenceTokenizerBuilder::new().whitespace(policy).build();
+    let sentences = tokenizer.tokenize_with_spans(document);
+    let texts: Vec<&str> = sentences.iter().map(|s| s.text.as_ref()).collect();
+    assert_eq!(
+        vec![
+            "　吾輩は猫である。",
+            "名前はまだ無い。",
+            "　どこで生れたか。"
+        ],
+        texts
+    );
+    assert_eq!(1..28, sentences[0].span);
+    assert_eq!(29..54, sentences[1].span);
+    assert_eq!(56..83, sentences[2].span);
+
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(LineBreaks::Split)
+        .whitespace(policy.trim_document(false).ideographic_space(false))
+        .build();
+    assert_eq!(
+        vec![
+            "　吾輩は猫である。",
+            "名前は",
+            "まだ無い。",
+            "　どこで生れたか。"
+        ],
+        tokenizer.tokenize(document)
+    );
+    let sentences = tokenizer.tokenize_with_spans(document);
+    for sentence in &sentences {
+        assert_eq!(sentence.text, &document[sentence.span.clone()]);
+    }
+}
+
+#[test]
+fn test_auto_line_breaks() {
+    let document = concat!(
+        "第1章　はじめに\n",
+        "　吾輩は猫である。名前はまだ無い。どこで生れ\n",
+        "たかとんと見当がつかぬ。何でも薄暗いじめじめ\n",
+        "した所でニャーニャー泣いていた事だけは記憶し\n",
+        "ている。\r\n",
+        "\n",
+        "吾輩はここで始めて人間というものを見た",
+    );
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(LineBreaks::Auto)
+        .build();
+    let expected = vec![
+        "第1章　はじめに",
+        "　吾輩は猫である。",
+        "名前はまだ無い。",
+        "どこで生れたかとんと見当がつかぬ。",
+        "何でも薄暗いじめじめした所でニャーニャー泣いていた事だけは記憶している。",
+        "吾輩はここで始めて人間というものを見た",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!("第1章　はじめに", &document[sentences[0].span.clone()]);
+    assert_eq!(
+        "吾輩はここで始めて人間というものを見た",
+        &document[sentences[5].span.clone()]
+    );
+}
+
+#[test]
+fn test_join_latin_words() {
+    let document = "This is a\nsentence。日本語の\n文です。Version\r\n2 です。";
+    let tokenizer = SentenceTokenizerBuilder::new().eos_chars(&['。']).build();
+    assert_eq!(
+        vec![
+            "This is a sentence。",
+            "日本語の文です。",
+            "Version 2 です。"
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(vec![9], sentences[0].inserted);
+    assert!(sentences[1].inserted.is_empty());
+    assert_eq!(vec![7], sentences[2].inserted);
+    for sentence in &sentences {
+        for (i, ch) in sentence.text.char_indices() {
+            let j = sentence.source_offset(document, i);
+            if sentence.inserted.contains(&i) {
+                assert!(document[j..].starts_with(['\n', '\r']));
+            } else {
+                assert!(document[j..].starts_with(ch));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_headings() {
+    let document = "第1章　はじめに\n本書の目的を述べる。\n1.2 背景\n従来の手法には\n問題があった。\n\nまとめ\n\n以上です。";
+    let tokenizer = SentenceTokenizerBuilder::new().headings(true).build();
+    assert_eq!(
+        vec![
+            "第1章　はじめに",
+            "本書の目的を述べる。",
+            "1.2 背景",
+            "従来の手法には問題があった。",
+            "まとめ",
+            "以上です。"
+        ],
+        tokenizer.tokenize(document)
+    );
+    let kinds: Vec<SentenceKind> = tokenizer
+        .tokenize_with_spans(document)
+        .iter()
+        .map(|sentence| sentence.kind)
+        .collect();
+    assert_eq!(
+        vec![
+            SentenceKind::Heading,
+            SentenceKind::Text,
+            SentenceKind::Heading,
+            SentenceKind::Text,
+            SentenceKind::Heading,
+            SentenceKind::Text
+        ],
+        kinds
+    );
+
+    // without the option, the heading is joined with the next sentence
+    let tokenizer = SentenceTokenizerBuilder::new().build();
+    assert_eq!(
+        "第1章　はじめに本書の目的を述べる。",
+        tokenizer.tokenize(document)[0]
+    );
+    // wrapped lines are not headings
+    let tokenizer = SentenceTokenizerBuilder::new().headings(true).build();
+    assert_eq!(
+        vec!["吾輩は猫である。", "名前はまだない。"],
+        tokenizer.tokenize("吾輩は猫である。\n名前は\nまだない。")
+    );
+}
+
+#[test]
+fn test_list_items() {
+    let document = "手順は次の通り\n・材料を\n　用意する\n・切る\n(1) 皮をむく(丁寧に。)\n(2) 煮る。\n1) 火を止める";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .patterns(&[['(', ')'], ['「', '」']])
+        .list_items(true)
+        .build();
+    assert_eq!(
+        vec![
+            "手順は次の通り",
+            "・材料を　用意する",
+            "・切る",
+            "(1) 皮をむく(丁寧に。)",
+            "(2) 煮る。",
+            "1) 火を止める"
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(SentenceKind::Text, sentences[0].kind);
+    let markers: Vec<&str> = sentences[1..]
+        .iter()
+        .map(|sentence| {
+            assert_eq!(SentenceKind::ListItem, sentence.kind);
+            &document[sentence.marker.clone().unwrap()]
+        })
+        .collect();
+    assert_eq!(vec!["・", "・", "(1)", "(2)", "1)"], markers);
+
+    // enclosed numbers in the split mode
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(LineBreaks::Split)
+        .list_items(true)
+        .build();
+    let sentences = tokenizer.tokenize_with_spans("①最初に。\n②次に。");
+    assert_eq!(2, sentences.len());
+    assert!(sentences
+        .iter()
+        .all(|sentence| sentence.kind == SentenceKind::ListItem));
+
+    // terminators in markers are ignored by both entry points
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .eos_chars(&['。', '．'])
+        .line_breaks(LineBreaks::Split)
+        .list_items(true)
+        .build();
+    let document = "１．項目です。\n２．次です";
+    let texts: Vec<String> = tokenizer
+        .tokenize_with_spans(document)
+        .into_iter()
+        .map(|sentence| sentence.text.into_owned())
+        .collect();
+    assert_eq!(vec!["１．項目です。", "２．次です"], texts);
+    assert_eq!(texts, tokenizer.tokenize(document));
+}
+
+#[test]
+fn test_legal() {
+    let document = "第一章　総則\n（目的）\n第一条　この法律は、個人の権利を保護することを目的とする。\n（定義）\n第二条　この法律において「個人情報」とは、生存する個人に関する情報をいう。ただし、次に掲げるものを除く。\n一　国の機関\n二　地方公共団体\n２　前項の規定は\n準用する。";
+    let tokenizer = SentenceTokenizerBuilder::new().legal(true).build();
+    let expected = vec![
+        "第一章　総則",
+        "（目的）第一条　この法律は、個人の権利を保護することを目的とする。",
+        "（定義）第二条　この法律において「個人情報」とは、生存する個人に関する情報をいう。",
+        "ただし、次に掲げるものを除く。",
+        "一　国の機関",
+        "二　地方公共団体",
+        "２　前項の規定は準用する。",
+    ];
+    assert_eq!(expected, tokenizer.tokenize(document));
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(None, sentences[0].provision);
+    let provision = |article: &str, caption: &str, paragraph, item| Provision {
+        article: Some(article.to_string()),
+        caption: Some(caption.to_string()),
+        paragraph: Some(paragraph),
+        item,
+    };
+    let provisions: Vec<Provision> = sentences[1..]
+        .iter()
+        .map(|sentence| sentence.provision.clone().unwrap())
+        .collect();
+    assert_eq!(
+        vec![
+            provision("第一条", "目的", 1, None),
+            provision("第二条", "定義", 1, None),
+            provision("第二条", "定義", 1, None),
+            provision("第二条", "定義", 1, Some(1)),
+            provision("第二条", "定義", 1, Some(2)),
+            provision("第二条", "定義", 2, None),
+        ],
+        provisions
+    );
+
+    // the split mode keeps the caption with its article
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(LineBreaks::Split)
+        .legal(true)
+        .build();
+    let sentences = tokenizer.tokenize(document);
+    assert_eq!(expected[1], sentences[1]);
+    assert_eq!("２　前項の規定は", sentences[6]);
+    assert_eq!("準用する。", sentences[7]);
+}
+
+#[test]
+fn test_speech_endings() {
+    let document = "本日はお越しいただきありがとうございます それでは始めましょう\nまず資料をご覧くださいこちらが売上ですよね でも来月は未定です。よろしいですか";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .speech_endings(&SPEECH_ENDINGS)
+        .build();
+    assert_eq!(
+        vec![
+            "本日はお越しいただきありがとうございます",
+            " それでは始めましょう",
+            "まず資料をご覧くださいこちらが売上ですよね",
+            " でも来月は未定です。",
+            "よろしいですか",
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(
+        "本日はお越しいただきありがとうございます",
+        &document[sentences[0].span.clone()]
+    );
+
+    // a custom list, in the split mode
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .line_breaks(LineBreaks::Split)
+        .speech_endings(&["だ"])
+        .whitespace(WhitespacePolicy::new().trim_sentences(true))
+        .build();
+    assert_eq!(
+        vec!["いい天気だね", "散歩しよう"],
+        tokenizer.tokenize("いい天気だね 散歩しよう")
+    );
+    assert_eq!(vec!["雨です 傘は"], tokenizer.tokenize("雨です 傘は"));
+}
+
+#[test]
+fn test_social_media() {
+    let document = "ライブ最高😂😂次も行く（笑）ｗｗｗ楽しみ♪ 明日は仕事　がんばる(´・ω・｀)もう寝る！🙏www.example.com";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .eos_chars(&['。', '！'])
+        .social_media(true)
+        .build();
+    assert_eq!(
+        vec![
+            "ライブ最高😂😂",
+            "次も行く（笑）ｗｗｗ",
+            "楽しみ♪",
+            " 明日は仕事　",
+            "がんばる(´・ω・｀)",
+            "もう寝る！🙏",
+            "www.example.com",
+        ],
+        tokenizer.tokenize(document)
+    );
+
+    // the brackets of kaomoji are not matched, and brackets still keep terminators
+    let document = "「最高（＾ω＾）」って言った😊";
+    assert_eq!(vec![document], tokenizer.tokenize(document));
+    let document = "はい（^^;)そうです";
+    assert_eq!(vec!["はい（^^;)", "そうです"], tokenizer.tokenize(document));
+
+    // kaomoji do not span lines, so line breaks are joined or split as usual
+    let document = "(\n|・\u{3000}！①)-----｜|漢《条";
+    assert!(tokenizer
+        .tokenize(document)
+        .iter()
+        .all(|sentence| !sentence.contains('\n')));
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .eos_chars(&['。', '！'])
+        .line_breaks(LineBreaks::Split)
+        .social_media(true)
+        .build();
+    assert_eq!(
+        vec!["(", "|・\u{3000}", "！", "①)-----｜|漢《条"],
+        tokenizer.tokenize(document)
+    );
+}
+
+#[test]
+fn test_quote_eos() {
+    let document = "他说：“今天\n天气很好。”然后就出门了。“好”。";
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .patterns(&[['“', '”']])
+        .quote_eos(true)
+        .build();
+    assert_eq!(
+        vec!["他说：“今天天气很好。”", "然后就出门了。", "“好”。"],
+        tokenizer.tokenize(document)
+    );
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!(
+        "他说：“今天\n天气很好。”",
+        &document[sentences[0].span.clone()]
+    );
+
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .patterns(&[['“', '”']])
+        .build();
+    assert_eq!(
+        vec!["他说：“今天天气很好。”然后就出门了。", "“好”。"],
+        tokenizer.tokenize(document)
+    );
+}
+
+#[test]
+fn test_latin() {
+    let document = "本章では手法を述べる。Dr. Smith measured 3.5 kg of it, i.e. a lot. It was \"huge!\" But why... nobody knows. 次の節に進む。\nThe end.";
+    let tokenizer = SentenceTokenizerBuilder::new().latin(true).build();
+    assert_eq!(
+        vec![
+            "本章では手法を述べる。",
+            "Dr. Smith measured 3.5 kg of it, i.e. a lot.",
+            "It was \"huge!\"",
+            "But why... nobody knows.",
+            "次の節に進む。",
+            "The end.",
+        ],
+        tokenizer.tokenize(document)
+    );
+    let sentences = tokenizer.tokenize_with_spans(document);
+    assert_eq!("It was \"huge!\"", &document[sentences[2].span.clone()]);
+    assert_eq!("次の節に進む。", &document[sentences[4].span.clone()]);
+
+    // the whitespace policy decides for every sentence
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .latin(true)
+        .whitespace(WhitespacePolicy::new())
+        .build();
+    assert_eq!(
+        vec!["It was here.", " これです。", " Yes."],
+        tokenizer.tokenize("It was here. これです。 Yes.")
+    );
+
+    // `.` and `!` as terminators follow the Latin rules in Latin text only
+    let tokenizer = SentenceTokenizerBuilder::new()
+        .eos_chars(&['。', '.', '!'])
+        .latin(true)
+        .build();
+    assert_eq!(
+        vec!["Mr. Tanaka said hi!", "本当.", "です。"],
+        tokenizer.tokenize("Mr. Tanaka said hi! 本当.です。")
+    );
+}